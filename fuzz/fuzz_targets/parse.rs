@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tmi_parser::Message;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(msg) = std::str::from_utf8(data) {
+        if let Ok(msg) = Message::parse(msg) {
+            let _ = msg.unparse();
+        }
+    }
+});
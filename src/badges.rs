@@ -0,0 +1,122 @@
+//! Badges of TMI messages.
+
+use crate::{Message, TagValue};
+use std::str::Split;
+
+/// Parsed value of the `badges` and `badge-info` tags.
+/// Badges are stored as a list of `<name>/<version>` pairs separated by commas.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let badges = Badges::new("staff/1,bits/1000");
+/// # assert!(badges.has("staff"));
+/// # assert_eq!(badges.get("bits"), Some("1000"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Badges<'a> {
+    raw: &'a str,
+}
+
+impl<'a> Badges<'a> {
+    /// Returns the badges contained in the given [`&str`].
+    pub fn new(raw: &'a str) -> Badges<'a> {
+        Badges { raw }
+    }
+
+    /// Returns an iterator over the `(name, version)` pairs.
+    pub fn iter(&self) -> BadgesIter<'a> {
+        BadgesIter {
+            iter: self.raw.split(','),
+        }
+    }
+
+    /// Returns the version of the badge with the given name.
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        self.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, ver)| ver)
+    }
+
+    /// Returns true if a badge with the given name is present.
+    pub fn has(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns the exact number of subscribed months.
+    /// Meaningful only for the `badge-info` tag.
+    pub fn subscriber_months(&self) -> Option<u32> {
+        self.get("subscriber").and_then(|ver| ver.parse().ok())
+    }
+
+    /// Returns true if there are no badges.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+impl<'a> IntoIterator for Badges<'a> {
+    type Item = (&'a str, &'a str);
+    type IntoIter = BadgesIter<'a>;
+
+    fn into_iter(self) -> BadgesIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the `(name, version)` pairs of [`Badges`].
+#[derive(Debug, Clone)]
+pub struct BadgesIter<'a> {
+    iter: Split<'a, char>,
+}
+
+impl<'a> Iterator for BadgesIter<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<(&'a str, &'a str)> {
+        for badge in &mut self.iter {
+            if badge.is_empty() {
+                continue;
+            }
+
+            return Some(match badge.find('/') {
+                Some(idx) => (&badge[..idx], &badge[(idx + 1)..]),
+                None => (badge, ""),
+            });
+        }
+
+        None
+    }
+}
+
+impl<'a> TagValue<'a> {
+    /// Returns the badges contained in a string or empty value, otherwise None.
+    pub fn badges(&self) -> Option<Badges<'_>> {
+        match self {
+            TagValue::String(val) => Some(Badges::new(val)),
+            TagValue::None => Some(Badges::new("")),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Returns the parsed `badges` tag of the message, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let msg = Message::parse("@badges=moderator/1 :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :hi").unwrap();
+    /// # assert!(msg.badges().unwrap().has("moderator"));
+    /// ```
+    pub fn badges(&self) -> Option<Badges<'_>> {
+        self.tags()?.get("badges")?.badges()
+    }
+
+    /// Returns the parsed `badge-info` tag of the message, if any.
+    pub fn badge_info(&self) -> Option<Badges<'_>> {
+        self.tags()?.get("badge-info")?.badges()
+    }
+}
@@ -0,0 +1,106 @@
+//! Capabilities of TMI messages.
+
+use crate::Message;
+use std::fmt;
+use std::str::SplitWhitespace;
+
+/// Possible capabilities negotiated with the TMI server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability<'a> {
+    /// Represents the `twitch.tv/tags` capability.
+    Tags,
+    /// Represents the `twitch.tv/commands` capability.
+    Commands,
+    /// Represents the `twitch.tv/membership` capability.
+    Membership,
+    /// Represents any other capability.
+    Other(&'a str),
+}
+
+impl<'a> Capability<'a> {
+    /// Returns a Capability variant based on the given [`&str`].
+    pub fn new(cap: &'a str) -> Capability<'a> {
+        match cap {
+            "twitch.tv/tags" => Capability::Tags,
+            "twitch.tv/commands" => Capability::Commands,
+            "twitch.tv/membership" => Capability::Membership,
+            _ => Capability::Other(cap),
+        }
+    }
+
+    /// Returns the name of the capability.
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            Capability::Tags => "twitch.tv/tags",
+            Capability::Commands => "twitch.tv/commands",
+            Capability::Membership => "twitch.tv/membership",
+            Capability::Other(cap) => cap,
+        }
+    }
+}
+
+impl<'a> fmt::Display for Capability<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Iterator over a space separated list of capabilities.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let mut caps = Capabilities::new("twitch.tv/tags twitch.tv/commands");
+/// # assert_eq!(caps.next(), Some(Capability::Tags));
+/// # assert_eq!(caps.next(), Some(Capability::Commands));
+/// # assert_eq!(caps.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Capabilities<'a> {
+    iter: SplitWhitespace<'a>,
+}
+
+impl<'a> Capabilities<'a> {
+    /// Returns an iterator over the given capabilities list.
+    pub fn new(caps: &'a str) -> Capabilities<'a> {
+        Capabilities {
+            iter: caps.split_whitespace(),
+        }
+    }
+}
+
+impl<'a> Iterator for Capabilities<'a> {
+    type Item = Capability<'a>;
+
+    fn next(&mut self) -> Option<Capability<'a>> {
+        self.iter.next().map(Capability::new)
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Returns the capabilities carried by a CAP message, otherwise None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let msg = Message::parse(":tmi.twitch.tv CAP * ACK :twitch.tv/tags twitch.tv/commands").unwrap();
+    /// let granted = msg.caps().unwrap().collect::<Vec<_>>();
+    /// # assert_eq!(granted, [Capability::Tags, Capability::Commands]);
+    /// ```
+    pub fn caps(&self) -> Option<Capabilities<'a>> {
+        match self {
+            Message::CapReq { req, .. }
+            | Message::CapAck { req, .. }
+            | Message::CapNak { req, .. }
+            | Message::CapLs {
+                caps: Some(req), ..
+            }
+            | Message::CapList {
+                caps: Some(req), ..
+            } => Some(Capabilities::new(req)),
+            _ => None,
+        }
+    }
+}
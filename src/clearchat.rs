@@ -0,0 +1,70 @@
+//! Actions of CLEARCHAT messages.
+
+use crate::typed_tags::{number, string};
+use crate::Message;
+
+/// Possible actions of CLEARCHAT messages, decoded from the user parameter
+/// and the `ban-duration` and `target-user-id` tags.
+///
+/// The `room-id` and `tmi-sent-ts` tags are available through [`Message::clearchat_tags`].
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let msg = Message::parse("@ban-duration=600;room-id=1337;target-user-id=1338 \
+///                           :tmi.twitch.tv CLEARCHAT #dallas :ronni").unwrap();
+/// # assert!(matches!(msg.clearchat_action(), Some(ClearchatAction::Timeout { seconds: 600, .. })));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClearchatAction<'a> {
+    /// Represents the removal of every message of the channel.
+    ClearAll,
+    /// Represents a user permanently banned from the channel.
+    Ban {
+        login: &'a str,
+        user_id: Option<&'a str>,
+    },
+    /// Represents a user temporarily banned from the channel.
+    Timeout {
+        login: &'a str,
+        user_id: Option<&'a str>,
+        seconds: u32,
+    },
+}
+
+impl<'a> Message<'a> {
+    /// Returns the action of a [`Message::Clearchat`], otherwise None.
+    ///
+    /// A user without a `ban-duration` tag is considered permanently banned,
+    /// while a malformed `ban-duration` tag returns None.
+    pub fn clearchat_action(&self) -> Option<ClearchatAction<'_>> {
+        match self {
+            Message::Clearchat { usr: None, .. } => Some(ClearchatAction::ClearAll),
+            Message::Clearchat {
+                tags,
+                usr: Some(login),
+                ..
+            } => {
+                let user_id = tags
+                    .as_ref()
+                    .and_then(|tags| string(tags, "target-user-id"));
+
+                match tags
+                    .as_ref()
+                    .filter(|tags| tags.contains_key("ban-duration"))
+                {
+                    Some(tags) => {
+                        number(tags, "ban-duration").map(|seconds| ClearchatAction::Timeout {
+                            login,
+                            user_id,
+                            seconds,
+                        })
+                    }
+                    None => Some(ClearchatAction::Ban { login, user_id }),
+                }
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Colors of user names.
+
+use crate::{Message, TagValue};
+use std::fmt;
+
+/// Default colors assigned by Twitch to users who never set one.
+const DEFAULT_COLORS: [Rgb; 15] = [
+    Rgb::new(0xFF, 0x00, 0x00),
+    Rgb::new(0x00, 0x00, 0xFF),
+    Rgb::new(0x00, 0x80, 0x00),
+    Rgb::new(0xB2, 0x22, 0x22),
+    Rgb::new(0xFF, 0x7F, 0x50),
+    Rgb::new(0x9A, 0xCD, 0x32),
+    Rgb::new(0xFF, 0x45, 0x00),
+    Rgb::new(0x2E, 0x8B, 0x57),
+    Rgb::new(0xDA, 0xA5, 0x20),
+    Rgb::new(0xD2, 0x69, 0x1E),
+    Rgb::new(0x5F, 0x9E, 0xA0),
+    Rgb::new(0x1E, 0x90, 0xFF),
+    Rgb::new(0xFF, 0x69, 0xB4),
+    Rgb::new(0x8A, 0x2B, 0xE2),
+    Rgb::new(0x00, 0xFF, 0x7F),
+];
+
+/// Minimum contrast ratio between a readable color and its background.
+pub const MIN_CONTRAST: f64 = 4.5;
+
+/// Represents a color of the `color` tag.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let color = Rgb::from_hex("#0D4200").unwrap();
+/// # assert_eq!(color, Rgb::new(0x0D, 0x42, 0x00));
+/// # assert_eq!(color.to_string(), "#0D4200");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Returns the color with the given components.
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    /// Returns the color of the given `#RRGGBB` string.
+    /// Returns None if the string is not a `#` followed by exactly six hexadecimal digits.
+    pub fn from_hex(hex: &str) -> Option<Rgb> {
+        let hex = hex.strip_prefix('#')?;
+
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let num = u32::from_str_radix(hex, 16).ok()?;
+        Some(Rgb::new((num >> 16) as u8, (num >> 8) as u8, num as u8))
+    }
+
+    /// Returns the default color assigned by Twitch to the user with the given login.
+    ///
+    /// The color is picked from a palette of 15 colors, indexed by the sum of
+    /// the first and the last character of the login.
+    pub fn default_for(login: &str) -> Rgb {
+        let first = login.chars().next().map_or(0, u32::from);
+        let last = login.chars().next_back().map_or(0, u32::from);
+
+        DEFAULT_COLORS[((first + last) % DEFAULT_COLORS.len() as u32) as usize]
+    }
+
+    /// Returns the relative luminance of the color, from 0 (black) to 1 (white).
+    pub fn luminance(&self) -> f64 {
+        fn linear(c: u8) -> f64 {
+            let c = f64::from(c) / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// Returns the contrast ratio between the two colors, from 1 to 21.
+    pub fn contrast(&self, other: Rgb) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Returns the color adjusted to be readable on the given background.
+    ///
+    /// Colors with a contrast ratio below [`MIN_CONTRAST`] are mixed with white on dark
+    /// backgrounds and with black on light backgrounds, in steps of 10%, until readable.
+    pub fn readable_on(&self, background: Rgb) -> Rgb {
+        let target = if background.luminance() < 0.5 {
+            Rgb::new(0xFF, 0xFF, 0xFF)
+        } else {
+            Rgb::new(0x00, 0x00, 0x00)
+        };
+
+        (0..=10)
+            .map(|step| self.mix(target, f64::from(step) / 10.0))
+            .find(|color| color.contrast(background) >= MIN_CONTRAST)
+            .unwrap_or(target)
+    }
+
+    /// Helper function for mixing two colors, with the given weight of the other color.
+    fn mix(&self, other: Rgb, weight: f64) -> Rgb {
+        let mix =
+            |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * weight).round() as u8;
+        Rgb::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Returns the color of the user name of the message sender, falling back to
+    /// the default color of their login when the `color` tag is empty.
+    ///
+    /// Returns None for messages without a sender.
+    pub fn color(&self) -> Option<Rgb> {
+        if let Some(TagValue::Color(color)) = self.tags().and_then(|tags| tags.get("color")) {
+            return Some(*color);
+        }
+
+        match self {
+            Message::Privmsg { .. } | Message::Whisper { .. } => self.login().map(Rgb::default_for),
+            Message::Usernotice {
+                tags: Some(tags), ..
+            } => match tags.get("login")? {
+                TagValue::String(login) => Some(Rgb::default_for(login)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
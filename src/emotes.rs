@@ -0,0 +1,216 @@
+//! Emotes of TMI messages.
+
+use crate::{Message, TagValue};
+use std::ops::Range;
+use std::str::Split;
+
+/// Parsed value of the `emotes` tag.
+/// Emotes are stored as `<id>:<start>-<end>,<start>-<end>` lists separated by slashes.
+///
+/// Positions count Unicode scalar values (Rust [`char`]s) of the message, not bytes
+/// nor UTF-16 code units, and both ends are inclusive.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let emotes = Emotes::new("25:0-4,12-16/1902:6-10");
+/// let kappa = emotes.iter().next().unwrap();
+/// # assert_eq!(kappa.id, "25");
+/// # assert_eq!(kappa.ranges().collect::<Vec<_>>(), [0..5, 12..17]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Emotes<'a> {
+    raw: &'a str,
+}
+
+impl<'a> Emotes<'a> {
+    /// Returns the emotes contained in the given [`&str`].
+    pub fn new(raw: &'a str) -> Emotes<'a> {
+        Emotes { raw }
+    }
+
+    /// Returns an iterator over the emotes.
+    pub fn iter(&self) -> EmotesIter<'a> {
+        EmotesIter {
+            iter: self.raw.split('/'),
+        }
+    }
+
+    /// Returns true if there are no emotes.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Splits the message into text and emote fragments.
+    ///
+    /// Ranges out of the message bounds or overlapping a previous emote are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let emotes = Emotes::new("25:0-4");
+    /// let frags = emotes.fragments("Kappa hi");
+    /// # assert_eq!(frags, [Fragment::Emote { id: "25", text: "Kappa" }, Fragment::Text(" hi")]);
+    /// ```
+    pub fn fragments<'b>(&self, msg: &'b str) -> Vec<Fragment<'b>>
+    where
+        'a: 'b,
+    {
+        let mut ranges = self
+            .iter()
+            .flat_map(|emote| emote.ranges().map(move |range| (emote.id, range)))
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|(_, range)| range.start);
+
+        // Byte offset of every char, plus the end of the message.
+        let offs = msg
+            .char_indices()
+            .map(|(off, _)| off)
+            .chain(Some(msg.len()))
+            .collect::<Vec<_>>();
+
+        let mut frags = Vec::with_capacity(ranges.len() * 2 + 1);
+        let mut last = 0;
+
+        for (id, range) in ranges {
+            if range.start < last || range.end >= offs.len() {
+                continue;
+            }
+
+            if range.start > last {
+                frags.push(Fragment::Text(&msg[offs[last]..offs[range.start]]));
+            }
+
+            frags.push(Fragment::Emote {
+                id,
+                text: &msg[offs[range.start]..offs[range.end]],
+            });
+
+            last = range.end;
+        }
+
+        if last + 1 < offs.len() {
+            frags.push(Fragment::Text(&msg[offs[last]..]));
+        }
+
+        frags
+    }
+}
+
+impl<'a> IntoIterator for Emotes<'a> {
+    type Item = Emote<'a>;
+    type IntoIter = EmotesIter<'a>;
+
+    fn into_iter(self) -> EmotesIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the [`Emote`]s of [`Emotes`].
+#[derive(Debug, Clone)]
+pub struct EmotesIter<'a> {
+    iter: Split<'a, char>,
+}
+
+impl<'a> Iterator for EmotesIter<'a> {
+    type Item = Emote<'a>;
+
+    fn next(&mut self) -> Option<Emote<'a>> {
+        for emote in &mut self.iter {
+            if let Some(idx) = emote.find(':') {
+                return Some(Emote {
+                    id: &emote[..idx],
+                    raw: &emote[(idx + 1)..],
+                });
+            }
+        }
+
+        None
+    }
+}
+
+/// Single emote of the `emotes` tag, with all its occurrences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Emote<'a> {
+    /// Identifier of the emote.
+    pub id: &'a str,
+    raw: &'a str,
+}
+
+impl<'a> Emote<'a> {
+    /// Returns an iterator over the char ranges of the emote occurrences.
+    /// Malformed ranges are skipped.
+    pub fn ranges(&self) -> impl Iterator<Item = Range<usize>> + 'a {
+        self.raw.split(',').filter_map(|range| {
+            let idx = range.find('-')?;
+            let start = range[..idx].parse::<usize>().ok()?;
+            let end = range[(idx + 1)..].parse::<usize>().ok()?;
+
+            if start <= end {
+                end.checked_add(1).map(|end| start..end)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Fragment of a message split by [`Emotes::fragments`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fragment<'a> {
+    /// Represents plain text.
+    Text(&'a str),
+    /// Represents an emote and the text it replaces.
+    Emote { id: &'a str, text: &'a str },
+}
+
+impl<'a> TagValue<'a> {
+    /// Returns the emotes contained in a string or empty value, otherwise None.
+    pub fn emotes(&self) -> Option<Emotes<'_>> {
+        match self {
+            TagValue::String(val) => Some(Emotes::new(val)),
+            TagValue::None => Some(Emotes::new("")),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Returns the parsed `emotes` tag of the message, if any.
+    pub fn emotes(&self) -> Option<Emotes<'_>> {
+        self.tags()?.get("emotes")?.emotes()
+    }
+
+    /// Splits the text of a [`Message::Privmsg`] into text and emote fragments, otherwise None.
+    /// A message without the `emotes` tag is a single text fragment.
+    /// The `\x01ACTION` wrapper of `/me` messages is removed, since emote positions
+    /// count from the text it wraps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let msg = Message::parse("@emotes=25:0-4 PRIVMSG #dallas :Kappa hi").unwrap();
+    /// let frags = msg.fragments().unwrap();
+    /// # assert_eq!(frags, [Fragment::Emote { id: "25", text: "Kappa" }, Fragment::Text(" hi")]);
+    /// ```
+    pub fn fragments(&self) -> Option<Vec<Fragment<'_>>> {
+        match self {
+            Message::Privmsg { msg, .. } => {
+                let text = msg
+                    .strip_prefix("\x01ACTION ")
+                    .and_then(|text| text.strip_suffix('\x01'))
+                    .unwrap_or(msg);
+
+                Some(
+                    self.emotes()
+                        .unwrap_or_else(|| Emotes::new(""))
+                        .fragments(text),
+                )
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Directional encoding of TMI messages.
+
+use crate::{EncodeError, Message, Prefix};
+use std::borrow::Cow;
+use std::fmt;
+
+/// Endpoint used as the prefix of server messages without one.
+const ENDPOINT: &str = "tmi.twitch.tv";
+
+/// Maximum length in bytes of a message line, without tags and including the trailing `\r\n`.
+pub const MAX_LINE_LEN: usize = 512;
+
+/// Maximum length in bytes of the message tags, including the leading `@` and the trailing space.
+pub const MAX_TAGS_LEN: usize = 8191;
+
+/// Maximum length in bytes of the tags sent by a client, including the leading `@`
+/// and the trailing space.
+pub const MAX_CLIENT_TAGS_LEN: usize = 4094;
+
+/// Maximum length in characters of a chat message.
+pub const MAX_MESSAGE_CHARS: usize = 500;
+
+/// Possible senders of an encoded message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Messages sent by a client to the server, such as a bot.
+    /// They are written without prefix, and only with the tags a client can send.
+    Client,
+    /// Messages sent by the server to a client, such as a mock server.
+    /// They are written with their prefix, falling back to `tmi.twitch.tv`.
+    Server,
+}
+
+/// Sink measuring the written length and looking for forbidden characters.
+#[derive(Default)]
+pub(crate) struct Checker {
+    pub(crate) len: usize,
+    pub(crate) invalid: bool,
+}
+
+impl fmt::Write for Checker {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.len += s.len();
+        self.invalid |= s.contains(&['\r', '\n', '\0'][..]);
+        Ok(())
+    }
+}
+
+/// Helper function for checking if a tag can be sent by a client,
+/// such as the client-only tags prefixed by `+`.
+fn is_client_tag(key: &str) -> bool {
+    key.starts_with('+') || key == "client-nonce" || key == "reply-parent-msg-id"
+}
+
+/// Helper function for checking if a string can be sent as a middle parameter,
+/// that is not empty, without spaces and not starting with `:`.
+fn is_middle(param: &str) -> bool {
+    !param.is_empty() && !param.starts_with(':') && !param.contains(' ')
+}
+
+/// Helper function for checking if a string can be sent as a tag key.
+fn is_tag_key(key: &str) -> bool {
+    !key.is_empty() && !key.contains(&[' ', ';', '='][..])
+}
+
+/// Possible senders of each message.
+enum Sender {
+    Client,
+    Server,
+    /// Sent by either side, optionally with a prefix.
+    Any,
+    /// Sent by a client, and relayed by the server with the prefix of the user.
+    User,
+}
+
+impl<'a> Message<'a> {
+    /// Encodes a Message sent by the given [`Role`] and returns a newly allocated [`String`]
+    /// if successful, otherwise an [`EncodeError`].
+    ///
+    /// Unlike [`Message::unparse`], which writes the message as is, the prefix is omitted
+    /// for clients and required for the server, where server messages default to `tmi.twitch.tv`.
+    /// Clients only send the `+` prefixed, `client-nonce` and `reply-parent-msg-id` tags,
+    /// so the tags of a parsed server message, such as `badges`, are dropped.
+    /// Messages that cannot be sent by the given role are rejected, as well as messages
+    /// containing CR, LF or NUL characters, empty or malformed parameters and tag keys,
+    /// or exceeding the length limits of IRC and Twitch chat.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let msg = Message::Roomstate { tags: None, prefix: None, chan: "dallas" };
+    /// # assert_eq!(msg.encode(Role::Server).unwrap(), ":tmi.twitch.tv ROOMSTATE #dallas");
+    /// # assert!(msg.encode(Role::Client).is_err());
+    /// ```
+    pub fn encode(&self, role: Role) -> Result<String, EncodeError> {
+        let mut raw = String::new();
+        self.encode_to(&mut raw, role)?;
+        Ok(raw)
+    }
+
+    /// Encodes a Message sent by the given [`Role`] into the given [`fmt::Write`] sink.
+    /// See [`Message::encode`].
+    pub fn encode_to<W: fmt::Write>(&self, mut w: W, role: Role) -> Result<(), EncodeError> {
+        let prefix = match (role, self.sender()) {
+            (Role::Client, Sender::Server) => {
+                return Err(EncodeError::ServerOnly {
+                    command: self.command(),
+                })
+            }
+            (Role::Server, Sender::Client) => {
+                return Err(EncodeError::ClientOnly {
+                    command: self.command(),
+                })
+            }
+            (Role::Client, _) => None,
+            (Role::Server, Sender::Any) => self.prefix().copied(),
+            (Role::Server, Sender::User) => {
+                Some(
+                    self.prefix()
+                        .copied()
+                        .ok_or_else(|| EncodeError::MissingPrefix {
+                            command: self.command(),
+                        })?,
+                )
+            }
+            (Role::Server, Sender::Server) => {
+                Some(self.prefix().copied().unwrap_or(Prefix::Server(ENDPOINT)))
+            }
+        };
+
+        let keep: fn(&str) -> bool = match role {
+            Role::Client => is_client_tag,
+            Role::Server => |_| true,
+        };
+
+        let mut tags = Checker::default();
+        let mut line = Checker::default();
+
+        Self::unparse_tags(&mut tags, self.tags(), keep)?;
+        Self::unparse_prefix(&mut line, prefix.as_ref())?;
+        self.unparse_command(&mut line)?;
+
+        if tags.invalid || line.invalid {
+            return Err(EncodeError::InvalidCharacter {
+                command: self.command(),
+            });
+        }
+
+        if !self.middle().iter().flatten().all(|param| is_middle(param)) {
+            return Err(EncodeError::InvalidParam {
+                command: self.command(),
+            });
+        }
+
+        if let Some(tags) = self.tags() {
+            if tags.iter().any(|(key, _)| keep(key) && !is_tag_key(key)) {
+                return Err(EncodeError::InvalidTag {
+                    command: self.command(),
+                });
+            }
+        }
+
+        let max_tags = match role {
+            Role::Client => MAX_CLIENT_TAGS_LEN,
+            Role::Server => MAX_TAGS_LEN,
+        };
+
+        if tags.len > max_tags {
+            return Err(EncodeError::TagsTooLong {
+                command: self.command(),
+                len: tags.len,
+            });
+        }
+
+        if let Message::Privmsg { msg, .. } = self {
+            let len = msg.chars().count();
+
+            if len > MAX_MESSAGE_CHARS {
+                return Err(EncodeError::MessageTooLong {
+                    command: self.command(),
+                    len,
+                });
+            }
+        }
+
+        // Account for the trailing CR LF.
+        if line.len + 2 > MAX_LINE_LEN {
+            return Err(EncodeError::LineTooLong {
+                command: self.command(),
+                len: line.len + 2,
+            });
+        }
+
+        Self::unparse_tags(&mut w, self.tags(), keep)?;
+        Self::unparse_prefix(&mut w, prefix.as_ref())?;
+        self.unparse_command(&mut w)?;
+
+        Ok(())
+    }
+
+    /// Helper function for getting the sender of the message.
+    fn sender(&self) -> Sender {
+        match self {
+            Message::Pass { .. }
+            | Message::Nick { .. }
+            | Message::CapReq { .. }
+            | Message::CapEnd { .. }
+            | Message::CapLs { caps: None, .. }
+            | Message::CapList { caps: None, .. } => Sender::Client,
+            Message::Ping { .. } | Message::Pong { .. } => Sender::Any,
+            Message::Join { .. } | Message::Part { .. } | Message::Privmsg { .. } => Sender::User,
+            _ => Sender::Server,
+        }
+    }
+
+    /// Helper function for getting the parameters of the message written as single words.
+    fn middle(&self) -> [Option<&'a str>; 2] {
+        match self {
+            Message::Pass { pass, .. } => [Some(pass), None],
+            Message::Nick { nick, .. } => [Some(nick), None],
+            Message::Join { chan, .. }
+            | Message::Part { chan, .. }
+            | Message::Privmsg { chan, .. }
+            | Message::Clearchat { chan, .. }
+            | Message::Clearmsg { chan, .. }
+            | Message::Roomstate { chan, .. }
+            | Message::Usernotice { chan, .. }
+            | Message::Userstate { chan, .. } => [Some(chan), None],
+            Message::Notice { chan, .. } => [*chan, None],
+            Message::Whisper { to, .. } => [Some(to), None],
+            Message::HosttargetStart { host, chan, .. } => [Some(host), Some(chan)],
+            Message::HosttargetEnd { host, .. } => [Some(host), None],
+            _ => [None, None],
+        }
+    }
+
+    /// Helper function for getting the command name of the message,
+    /// or the 3-digit code of numeric messages.
+    fn command(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            Message::Ping { .. } => "PING",
+            Message::Pong { .. } => "PONG",
+            Message::CapReq { .. }
+            | Message::CapAck { .. }
+            | Message::CapNak { .. }
+            | Message::CapLs { .. }
+            | Message::CapList { .. }
+            | Message::CapEnd { .. } => "CAP",
+            Message::Pass { .. } => "PASS",
+            Message::Nick { .. } => "NICK",
+            Message::Join { .. } => "JOIN",
+            Message::Part { .. } => "PART",
+            Message::Privmsg { .. } => "PRIVMSG",
+            Message::Whisper { .. } => "WHISPER",
+            Message::Clearchat { .. } => "CLEARCHAT",
+            Message::Clearmsg { .. } => "CLEARMSG",
+            Message::HosttargetStart { .. } | Message::HosttargetEnd { .. } => "HOSTTARGET",
+            Message::Notice { .. } => "NOTICE",
+            Message::Reconnect { .. } => "RECONNECT",
+            Message::Roomstate { .. } => "ROOMSTATE",
+            Message::Usernotice { .. } => "USERNOTICE",
+            Message::Userstate { .. } => "USERSTATE",
+            Message::Numeric { code, .. } => return Cow::Owned(format!("{:03}", code)),
+            Message::GlobalUserstate { .. } => "GLOBALUSERSTATE",
+        })
+    }
+}
@@ -0,0 +1,236 @@
+//! Errors for TMI messages.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Possible causes of a failed [`Message::parse`](crate::Message::parse).
+///
+/// Every variant, except [`ParseError::Empty`], carries the byte offset
+/// in the original input at which the problem was detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The message is empty or only made of whitespace.
+    Empty,
+    /// The message tags are malformed.
+    MalformedTags { offset: usize },
+    /// The message prefix is malformed.
+    MalformedPrefix { offset: usize },
+    /// The message command is not recognized.
+    UnknownCommand { offset: usize, command: String },
+    /// The message lacks a prefix required by the command.
+    MissingPrefix {
+        offset: usize,
+        command: &'static str,
+    },
+    /// The message lacks the `#<channel>` parameter.
+    MissingChannel {
+        offset: usize,
+        command: &'static str,
+    },
+    /// The message lacks the ` :<trailing>` parameter.
+    MissingTrailing {
+        offset: usize,
+        command: &'static str,
+    },
+    /// The message parameters are not valid for the command.
+    MalformedParams {
+        offset: usize,
+        command: &'static str,
+    },
+    /// The viewer count of a HOSTTARGET message is not a valid number.
+    InvalidViewers {
+        offset: usize,
+        command: &'static str,
+    },
+}
+
+impl ParseError {
+    /// Returns the byte offset at which the error was detected.
+    pub fn offset(&self) -> usize {
+        match self {
+            ParseError::Empty => 0,
+            ParseError::MalformedTags { offset }
+            | ParseError::MalformedPrefix { offset }
+            | ParseError::UnknownCommand { offset, .. }
+            | ParseError::MissingPrefix { offset, .. }
+            | ParseError::MissingChannel { offset, .. }
+            | ParseError::MissingTrailing { offset, .. }
+            | ParseError::MalformedParams { offset, .. }
+            | ParseError::InvalidViewers { offset, .. } => *offset,
+        }
+    }
+
+    /// Returns the command of the offending message, if it was reached.
+    pub fn command(&self) -> Option<&str> {
+        match self {
+            ParseError::Empty
+            | ParseError::MalformedTags { .. }
+            | ParseError::MalformedPrefix { .. } => None,
+            ParseError::UnknownCommand { command, .. } => Some(command),
+            ParseError::MissingPrefix { command, .. }
+            | ParseError::MissingChannel { command, .. }
+            | ParseError::MissingTrailing { command, .. }
+            | ParseError::MalformedParams { command, .. }
+            | ParseError::InvalidViewers { command, .. } => Some(command),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "Empty message."),
+            ParseError::MalformedTags { offset } => {
+                write!(f, "Malformed message tags at byte {}.", offset)
+            }
+            ParseError::MalformedPrefix { offset } => {
+                write!(f, "Malformed message prefix at byte {}.", offset)
+            }
+            ParseError::UnknownCommand { offset, command } => {
+                write!(f, "Unknown command {} at byte {}.", command, offset)
+            }
+            ParseError::MissingPrefix { offset, command } => {
+                write!(
+                    f,
+                    "Missing prefix in {} command at byte {}.",
+                    command, offset
+                )
+            }
+            ParseError::MissingChannel { offset, command } => {
+                write!(
+                    f,
+                    "Missing channel in {} command at byte {}.",
+                    command, offset
+                )
+            }
+            ParseError::MissingTrailing { offset, command } => write!(
+                f,
+                "Missing trailing parameter in {} command at byte {}.",
+                command, offset
+            ),
+            ParseError::MalformedParams { offset, command } => {
+                write!(f, "Malformed {} command at byte {}.", command, offset)
+            }
+            ParseError::InvalidViewers { offset, command } => write!(
+                f,
+                "Invalid viewer count in {} command at byte {}.",
+                command, offset
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(err: ParseError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Possible causes of a failed [`Message::encode`](crate::Message::encode).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The message can only be sent by a client, such as `PASS` or `CAP REQ`.
+    ClientOnly { command: Cow<'static, str> },
+    /// The message can only be sent by the server, such as `ROOMSTATE`.
+    ServerOnly { command: Cow<'static, str> },
+    /// The message is relayed by the server but lacks the prefix of the originating user.
+    MissingPrefix { command: Cow<'static, str> },
+    /// The message contains a carriage return, a line feed or a NUL character.
+    InvalidCharacter { command: Cow<'static, str> },
+    /// The message has an empty parameter, or one containing a space or starting with `:`,
+    /// such as a channel or a nickname.
+    InvalidParam { command: Cow<'static, str> },
+    /// The message has an empty tag key, or one containing a space, `;` or `=`.
+    InvalidTag { command: Cow<'static, str> },
+    /// The message tags are longer than [`MAX_TAGS_LEN`](crate::MAX_TAGS_LEN) bytes,
+    /// or [`MAX_CLIENT_TAGS_LEN`](crate::MAX_CLIENT_TAGS_LEN) bytes for a client.
+    TagsTooLong {
+        command: Cow<'static, str>,
+        len: usize,
+    },
+    /// The message line is longer than [`MAX_LINE_LEN`](crate::MAX_LINE_LEN) bytes.
+    LineTooLong {
+        command: Cow<'static, str>,
+        len: usize,
+    },
+    /// The chat message is longer than [`MAX_MESSAGE_CHARS`](crate::MAX_MESSAGE_CHARS) characters.
+    MessageTooLong {
+        command: Cow<'static, str>,
+        len: usize,
+    },
+    /// The sink failed to write the message.
+    Write,
+}
+
+impl EncodeError {
+    /// Returns the command of the offending message, if any.
+    pub fn command(&self) -> Option<&str> {
+        match self {
+            EncodeError::ClientOnly { command }
+            | EncodeError::ServerOnly { command }
+            | EncodeError::MissingPrefix { command }
+            | EncodeError::InvalidCharacter { command }
+            | EncodeError::InvalidParam { command }
+            | EncodeError::InvalidTag { command }
+            | EncodeError::TagsTooLong { command, .. }
+            | EncodeError::LineTooLong { command, .. }
+            | EncodeError::MessageTooLong { command, .. } => Some(command.as_ref()),
+            EncodeError::Write => None,
+        }
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::ClientOnly { command } => {
+                write!(f, "The {} command can only be sent by a client.", command)
+            }
+            EncodeError::ServerOnly { command } => {
+                write!(f, "The {} command can only be sent by the server.", command)
+            }
+            EncodeError::MissingPrefix { command } => {
+                write!(f, "Missing user prefix in {} command.", command)
+            }
+            EncodeError::InvalidCharacter { command } => {
+                write!(f, "Invalid character in {} command.", command)
+            }
+            EncodeError::InvalidParam { command } => {
+                write!(f, "Invalid parameter in {} command.", command)
+            }
+            EncodeError::InvalidTag { command } => {
+                write!(f, "Invalid tag key in {} command.", command)
+            }
+            EncodeError::TagsTooLong { command, len } => {
+                write!(f, "Tags too long in {} command, {} bytes.", command, len)
+            }
+            EncodeError::LineTooLong { command, len } => {
+                write!(f, "Line too long in {} command, {} bytes.", command, len)
+            }
+            EncodeError::MessageTooLong { command, len } => write!(
+                f,
+                "Message too long in {} command, {} characters.",
+                command, len
+            ),
+            EncodeError::Write => write!(f, "Failed to write the message."),
+        }
+    }
+}
+
+impl Error for EncodeError {}
+
+impl From<EncodeError> for io::Error {
+    fn from(err: EncodeError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidInput, err)
+    }
+}
+
+impl From<fmt::Error> for EncodeError {
+    fn from(_: fmt::Error) -> EncodeError {
+        EncodeError::Write
+    }
+}
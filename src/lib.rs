@@ -1,7 +1,35 @@
 //! TMI message parsing library.
 
+mod badges;
+mod cap;
+mod clearchat;
+mod color;
+mod emotes;
+mod encode;
+mod error;
+mod marks;
 mod message;
+mod notice;
+mod numeric;
+mod prefix;
+mod roomstate;
+mod split;
 mod tags;
+mod typed_tags;
+mod usernotice;
 
+pub use crate::badges::*;
+pub use crate::cap::*;
+pub use crate::clearchat::*;
+pub use crate::color::*;
+pub use crate::emotes::*;
+pub use crate::encode::*;
+pub use crate::error::*;
 pub use crate::message::*;
+pub use crate::notice::*;
+pub use crate::numeric::*;
+pub use crate::prefix::*;
+pub use crate::roomstate::*;
 pub use crate::tags::*;
+pub use crate::typed_tags::*;
+pub use crate::usernotice::*;
@@ -1,8 +1,8 @@
 //! IRC-based TMI messages.
 
-use crate::{TagValue, Tags};
-use std::fmt::Write;
-use std::io::{Error, ErrorKind, Result};
+use crate::{Params, ParseError, Prefix, TagValue, Tags};
+use std::fmt;
+use std::io;
 
 /// Possible types of TMI messages.
 /// Unrecognized messages are handled by the associated [`parse`] function.
@@ -14,97 +14,181 @@ use std::io::{Error, ErrorKind, Result};
 #[derive(Debug, PartialEq)]
 pub enum Message<'a> {
     /// Represents a ping request message.
-    /// `PING :<endpoint>`
-    Ping,
+    /// `[:<prefix>] PING [:<token>]`
+    Ping {
+        prefix: Option<Prefix<'a>>,
+        token: Option<&'a str>,
+    },
     /// Represents a pong response message.
-    /// `PONG :<endpoint>`
-    Pong,
+    /// `[:<prefix>] PONG [:<token>]`
+    Pong {
+        prefix: Option<Prefix<'a>>,
+        token: Option<&'a str>,
+    },
     /// Represents a capability request message.
-    /// `CAP REQ :<capability>`
-    CapReq { req: &'a str },
+    /// `[:<prefix>] CAP REQ :<capability>`
+    CapReq {
+        prefix: Option<Prefix<'a>>,
+        req: &'a str,
+    },
     /// Represents a capability acknowledgement message.
-    /// `:<endpoint> CAP * ACK :<capability>`
-    CapAck { req: &'a str },
+    /// `:<prefix> CAP * ACK :<capability>`
+    CapAck {
+        prefix: Option<Prefix<'a>>,
+        req: &'a str,
+    },
+    /// Represents a capability rejection message.
+    /// `:<prefix> CAP * NAK :<capability>`
+    CapNak {
+        prefix: Option<Prefix<'a>>,
+        req: &'a str,
+    },
+    /// Represents a capability listing request or its reply.
+    /// `[:<prefix>] CAP LS [<version>]`
+    /// `:<prefix> CAP * LS [*] :<capability>`
+    /// The `*` marks every line of a long reply but the last, and sets `more`.
+    CapLs {
+        prefix: Option<Prefix<'a>>,
+        version: Option<&'a str>,
+        caps: Option<&'a str>,
+        more: bool,
+    },
+    /// Represents an enabled capabilities listing request or its reply.
+    /// `[:<prefix>] CAP LIST`
+    /// `:<prefix> CAP * LIST :<capability>`
+    CapList {
+        prefix: Option<Prefix<'a>>,
+        caps: Option<&'a str>,
+    },
+    /// Represents a capability negotiation end message.
+    /// `[:<prefix>] CAP END`
+    CapEnd { prefix: Option<Prefix<'a>> },
     /// Represents a password authentication message.
-    /// `PASS <password>`
-    /// `PASS oauth:<token>` (using Twitch OAuth tokens)
-    Pass { pass: &'a str },
+    /// `[:<prefix>] PASS <password>`
+    /// `[:<prefix>] PASS oauth:<token>` (using Twitch OAuth tokens)
+    Pass {
+        prefix: Option<Prefix<'a>>,
+        pass: &'a str,
+    },
     /// Represents a nickname authentication message.
-    /// `NICK <user>`
-    Nick { nick: &'a str },
+    /// `[:<prefix>] NICK <user>`
+    Nick {
+        prefix: Option<Prefix<'a>>,
+        nick: &'a str,
+    },
     /// Represents a join command message.
-    /// `JOIN #<channel>`
-    Join { chan: &'a str },
+    /// `[:<prefix>] JOIN #<channel>`
+    /// The prefix holds the joining user when using the `twitch.tv/membership` capability.
+    Join {
+        prefix: Option<Prefix<'a>>,
+        chan: &'a str,
+    },
     /// Represents a part command message.
-    /// `PART #<channel>`
-    Part { chan: &'a str },
+    /// `[:<prefix>] PART #<channel>`
+    /// The prefix holds the leaving user when using the `twitch.tv/membership` capability.
+    Part {
+        prefix: Option<Prefix<'a>>,
+        chan: &'a str,
+    },
     /// Represents a privmsg command message.
-    /// `[@<tags>] PRIVMSG #<channel> :<message>`
+    /// `[@<tags>] [:<prefix>] PRIVMSG #<channel> :<message>`
     Privmsg {
         tags: Option<Tags<'a>>,
+        prefix: Option<Prefix<'a>>,
         chan: &'a str,
         msg: &'a str,
     },
+    /// Represents a whisper message, whose sender is the required prefix.
+    /// `[@<tags>] :<prefix> WHISPER <user> :<message>`
+    Whisper {
+        tags: Option<Tags<'a>>,
+        from: Prefix<'a>,
+        to: &'a str,
+        msg: &'a str,
+    },
     /// Represents a clearchat command message.
-    /// `[@<tags>] :<endpoint> CLEARCHAT #<channel> [:<user>]`
+    /// `[@<tags>] :<prefix> CLEARCHAT #<channel> [:<user>]`
     Clearchat {
         tags: Option<Tags<'a>>,
+        prefix: Option<Prefix<'a>>,
         chan: &'a str,
         usr: Option<&'a str>,
     },
     /// Represents a clearmsg command message.
-    /// `[@<tags>] :<endpoint> CLEARMSG #<channel> :<message>`
+    /// `[@<tags>] :<prefix> CLEARMSG #<channel> :<message>`
     Clearmsg {
         tags: Option<Tags<'a>>,
+        prefix: Option<Prefix<'a>>,
         chan: &'a str,
         msg: &'a str,
     },
     /// Represents a hosttarget start message.
-    /// `:<endpoint> HOSTTARGET #<host> :<channel> [<viewers>]`
+    /// `:<prefix> HOSTTARGET #<host> :<channel> [<viewers>]`
     HosttargetStart {
+        prefix: Option<Prefix<'a>>,
         host: &'a str,
         chan: &'a str,
         view: Option<u32>,
     },
     /// Represents a hosttarget end message.
-    /// `:<endpoint> HOSTTARGET #<host> :- [<viewers>]`
-    HosttargetEnd { host: &'a str, view: Option<u32> },
+    /// `:<prefix> HOSTTARGET #<host> :- [<viewers>]`
+    HosttargetEnd {
+        prefix: Option<Prefix<'a>>,
+        host: &'a str,
+        view: Option<u32>,
+    },
     /// Represents a notice message.
-    /// `[@<tags>] :<endpoint> NOTICE #<channel> :<message>`
+    /// `[@<tags>] :<prefix> NOTICE #<channel> :<message>`
+    /// `:<prefix> NOTICE * :<message>` (global notices, such as login failures)
     Notice {
         tags: Option<Tags<'a>>,
-        chan: &'a str,
+        prefix: Option<Prefix<'a>>,
+        chan: Option<&'a str>,
         msg: &'a str,
     },
     /// Represents a reconnect request message.
-    /// `RECONNECT`
-    Reconnect,
+    /// `[:<prefix>] RECONNECT`
+    Reconnect { prefix: Option<Prefix<'a>> },
     /// Represents a roomstate message.
-    /// `[@<tags>] :<endpoint> ROOMSTATE #<channel>`
+    /// `[@<tags>] :<prefix> ROOMSTATE #<channel>`
     Roomstate {
         tags: Option<Tags<'a>>,
+        prefix: Option<Prefix<'a>>,
         chan: &'a str,
     },
     /// Represents a usernotice message.
-    /// `[@<tags>] :<endpoint> USERNOTICE #<channel> :<message>`
+    /// `[@<tags>] :<prefix> USERNOTICE #<channel> [:<message>]`
     Usernotice {
         tags: Option<Tags<'a>>,
+        prefix: Option<Prefix<'a>>,
         chan: &'a str,
-        msg: &'a str,
+        msg: Option<&'a str>,
     },
     /// Represents a userstate message.
-    /// `[@<tags>] :<endpoint> USERSTATE #<channel>`
+    /// `[@<tags>] :<prefix> USERSTATE #<channel>`
     Userstate {
         tags: Option<Tags<'a>>,
+        prefix: Option<Prefix<'a>>,
         chan: &'a str,
     },
+    /// Represents a numeric reply message, such as the welcome burst or the NAMES list.
+    /// `:<prefix> <code> <params>`
+    Numeric {
+        prefix: Option<Prefix<'a>>,
+        code: u16,
+        params: &'a str,
+    },
     /// Represents a global userstate message.
-    /// `[@<tags>] :<endpoint> GLOBALUSERSTATE`
-    GlobalUserstate { tags: Option<Tags<'a>> },
+    /// `[@<tags>] :<prefix> GLOBALUSERSTATE`
+    GlobalUserstate {
+        tags: Option<Tags<'a>>,
+        prefix: Option<Prefix<'a>>,
+    },
 }
 
 impl<'a> Message<'a> {
-    /// Parses a [`& str`] slice and returns a Message if successful, otherwise an [`std::io::Error`].
+    /// Parses a [`& str`] slice and returns a Message if successful, otherwise a [`ParseError`].
+    /// It never panics, whatever the input.
     ///
     /// # Examples
     ///
@@ -113,356 +197,598 @@ impl<'a> Message<'a> {
     /// let s = ":tmi.twitch.tv CLEARCHAT #dallas :ronni";
     /// let msg = Message::parse(s);
     /// ```
-    pub fn parse(msg: &'a str) -> Result<Message> {
-        if msg.len() < 5 {
-            return Err(Error::new(ErrorKind::Other, "Malformed message."));
+    pub fn parse(msg: &'a str) -> Result<Message<'a>, ParseError> {
+        let buf = msg.trim();
+        let start = msg.len() - msg.trim_start().len();
+
+        if buf.is_empty() {
+            return Err(ParseError::Empty);
         }
 
-        let buf = msg.trim();
         let (tags, off) = if let Some(buf) = buf.strip_prefix('@') {
-            Self::parse_tags(buf)?
+            Self::parse_tags(buf, start)?
         } else {
             (None, 0)
         };
 
         let mut rest = &buf[off..];
-        const ENDPOINT: &str = "tmi.twitch.tv ";
+        let mut off = start + off;
 
-        if let Some(off) = rest.find(ENDPOINT) {
-            rest = &rest[(off + ENDPOINT.len())..];
-        }
+        let prefix = if let Some(buf) = rest.strip_prefix(':') {
+            let idx = buf
+                .find(' ')
+                .ok_or(ParseError::MalformedPrefix { offset: off })?;
+            let prefix =
+                Prefix::parse(&buf[..idx]).ok_or(ParseError::MalformedPrefix { offset: off })?;
+
+            rest = &buf[(idx + 1)..];
+            off += idx + 2;
+
+            Some(prefix)
+        } else {
+            None
+        };
 
-        if let Some(off) = rest.find(' ') {
-            let cmd = &rest[..off];
-            let body = &rest[(off + 1)..];
+        if let Some(idx) = rest.find(' ') {
+            let cmd = &rest[..idx];
+            let body = &rest[(idx + 1)..];
 
-            Self::parse_command(cmd, body, tags)
+            Self::parse_command(cmd, body, off, tags, prefix)
         } else {
-            Self::parse_command(rest, "", tags)
+            Self::parse_command(rest, "", off, tags, prefix)
         }
     }
 
     /// Helper function for parsing message tags.
-    fn parse_tags(msg: &'a str) -> Result<(Option<Tags<'a>>, usize)> {
+    /// Tags end at the first space and may be followed by either a prefix or a command.
+    fn parse_tags(msg: &'a str, off: usize) -> Result<(Option<Tags<'a>>, usize), ParseError> {
         let mut map = Tags::default();
 
-        if let Some(idx) = msg.find(" :") {
+        if let Some(idx) = msg.find(' ') {
             let tag = &msg[..idx];
-            let toks = tag.split(';');
+            // Offset of the current tag, past the leading '@'.
+            let mut pos = off + 1;
+
+            for tok in tag.split(';') {
+                // A tag without '=' has an empty value.
+                let (key, val) = match tok.find('=') {
+                    Some(idx) => (&tok[..idx], &tok[(idx + 1)..]),
+                    None => (tok, ""),
+                };
+
+                if key.is_empty() {
+                    return Err(ParseError::MalformedTags { offset: pos });
+                }
 
-            for tok in toks {
-                let items = tok.split('=').collect::<Vec<_>>();
-                let key = items[0];
-                let val = items[1];
+                map.push_raw(key, TagValue::parse(key, val), tok);
+                pos += tok.len() + 1;
+            }
 
-                map.insert(key, TagValue::new(val));
+            // Tags are followed by a single space.
+            if msg[(idx + 1)..].starts_with(' ') {
+                return Err(ParseError::MalformedTags {
+                    offset: off + idx + 2,
+                });
             }
 
-            Ok((Some(map), idx + 3))
+            Ok((Some(map), idx + 2))
         } else {
-            Err(Error::new(ErrorKind::Other, "Parsing message tags failed."))
+            Err(ParseError::MalformedTags { offset: off })
+        }
+    }
+
+    /// Helper function for parsing the `#<channel>` parameter.
+    fn parse_channel(
+        body: &'a str,
+        off: usize,
+        command: &'static str,
+    ) -> Result<&'a str, ParseError> {
+        match body.strip_prefix('#') {
+            Some(chan) if !chan.is_empty() => Ok(chan),
+            _ => Err(ParseError::MissingChannel {
+                offset: off,
+                command,
+            }),
         }
     }
 
+    /// Helper function for splitting the ` :<trailing>` parameter from the body.
+    fn split_trailing(
+        body: &'a str,
+        off: usize,
+        command: &'static str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        body.find(" :")
+            .map(|idx| (&body[..idx], &body[(idx + 2)..]))
+            .ok_or(ParseError::MissingTrailing {
+                offset: off + body.len(),
+                command,
+            })
+    }
+
     /// Helper function for parsing message body base on the command.
-    fn parse_command(cmd: &'a str, body: &'a str, tags: Option<Tags<'a>>) -> Result<Message<'a>> {
+    /// The offset refers to the start of the command in the original message.
+    fn parse_command(
+        cmd: &'a str,
+        body: &'a str,
+        off: usize,
+        tags: Option<Tags<'a>>,
+        prefix: Option<Prefix<'a>>,
+    ) -> Result<Message<'a>, ParseError> {
+        let off_body = off + cmd.len() + usize::from(!body.is_empty());
+
         Ok(match cmd {
-            "PING" => Message::Ping,
-            "PONG" => Message::Pong,
+            "PING" => Message::Ping {
+                prefix,
+                token: Params::new(body).last(),
+            },
+            "PONG" => Message::Pong {
+                prefix,
+                token: Params::new(body).last(),
+            },
             "CAP" => {
-                let off = body
-                    .find(" :")
-                    .ok_or_else(|| Error::new(ErrorKind::Other, "Malformed CAP command."))?;
+                // The last parameter may also be sent without the leading ':'.
+                let (sub, req) = match Self::split_trailing(body, off_body, "CAP") {
+                    Ok((sub, req)) => (sub, Some(req)),
+                    Err(_) => match body.rfind(' ') {
+                        Some(idx) => (&body[..idx], Some(&body[(idx + 1)..])),
+                        None => (body, None),
+                    },
+                };
 
-                match &body[..off] {
-                    "REQ" => Message::CapReq {
-                        req: &body[(off + 2)..],
+                match (sub, req) {
+                    ("REQ", Some(req)) => Message::CapReq { prefix, req },
+                    ("* ACK", Some(req)) => Message::CapAck { prefix, req },
+                    ("* NAK", Some(req)) => Message::CapNak { prefix, req },
+                    ("LS", None) => Message::CapLs {
+                        prefix,
+                        version: None,
+                        caps: None,
+                        more: false,
                     },
-                    "* ACK" => Message::CapAck {
-                        req: &body[(off + 2)..],
+                    // Version of the capability negotiation, such as `CAP LS 302`.
+                    ("LS", Some(ver))
+                        if !ver.is_empty() && ver.bytes().all(|b| b.is_ascii_digit()) =>
+                    {
+                        Message::CapLs {
+                            prefix,
+                            version: Some(ver),
+                            caps: None,
+                            more: false,
+                        }
+                    }
+                    ("* LS", Some(_)) | ("* LS *", Some(_)) => Message::CapLs {
+                        prefix,
+                        version: None,
+                        caps: req,
+                        more: sub.ends_with('*'),
                     },
-                    _ => return Err(Error::new(ErrorKind::Other, "Malformed CAP command.")),
+                    ("LIST", None) | ("* LIST", Some(_)) => Message::CapList { prefix, caps: req },
+                    ("END", None) => Message::CapEnd { prefix },
+                    _ => {
+                        return Err(ParseError::MalformedParams {
+                            offset: off_body,
+                            command: "CAP",
+                        })
+                    }
                 }
             }
-            "PASS" => Message::Pass { pass: body },
-            "NICK" => Message::Nick { nick: body },
-            "JOIN" => Message::Join { chan: &body[1..] },
-            "PART" => Message::Part { chan: &body[1..] },
+            "PASS" => Message::Pass { prefix, pass: body },
+            "NICK" => Message::Nick { prefix, nick: body },
+            "JOIN" => Message::Join {
+                prefix,
+                chan: Self::parse_channel(body, off_body, "JOIN")?,
+            },
+            "PART" => Message::Part {
+                prefix,
+                chan: Self::parse_channel(body, off_body, "PART")?,
+            },
             "PRIVMSG" => {
-                let off = body
-                    .find(" :")
-                    .ok_or_else(|| Error::new(ErrorKind::Other, "Malformed PRIVMSG command."))?;
+                let (chan, msg) = Self::split_trailing(body, off_body, "PRIVMSG")?;
 
                 Message::Privmsg {
                     tags,
-                    chan: &body[1..off],
-                    msg: &body[(off + 2)..],
+                    prefix,
+                    chan: Self::parse_channel(chan, off_body, "PRIVMSG")?,
+                    msg,
+                }
+            }
+            "WHISPER" => {
+                let (to, msg) = Self::split_trailing(body, off_body, "WHISPER")?;
+
+                if to.is_empty() {
+                    return Err(ParseError::MalformedParams {
+                        offset: off_body,
+                        command: "WHISPER",
+                    });
+                }
+
+                Message::Whisper {
+                    tags,
+                    from: prefix.ok_or(ParseError::MissingPrefix {
+                        offset: off,
+                        command: "WHISPER",
+                    })?,
+                    to,
+                    msg,
                 }
             }
             "CLEARCHAT" => {
-                if let Some(off) = body.find(" :") {
-                    Message::Clearchat {
-                        tags,
-                        chan: &body[1..off],
-                        usr: Some(&body[(off + 2)..]),
-                    }
-                } else {
-                    Message::Clearchat {
-                        tags,
-                        chan: &body[1..],
-                        usr: None,
-                    }
+                let (chan, usr) = match Self::split_trailing(body, off_body, "CLEARCHAT") {
+                    Ok((chan, usr)) => (chan, Some(usr)),
+                    Err(_) => (body, None),
+                };
+
+                Message::Clearchat {
+                    tags,
+                    prefix,
+                    chan: Self::parse_channel(chan, off_body, "CLEARCHAT")?,
+                    usr,
                 }
             }
             "CLEARMSG" => {
-                let off = body
-                    .find(" :")
-                    .ok_or_else(|| Error::new(ErrorKind::Other, "Malformed CLEARMSG command."))?;
+                let (chan, msg) = Self::split_trailing(body, off_body, "CLEARMSG")?;
 
                 Message::Clearmsg {
                     tags,
-                    chan: &body[1..off],
-                    msg: &body[(off + 2)..],
+                    prefix,
+                    chan: Self::parse_channel(chan, off_body, "CLEARMSG")?,
+                    msg,
                 }
             }
             "HOSTTARGET" => {
-                if let Some(off) = body.find(" :-") {
-                    if body.len() > off + 5 {
-                        if let Ok(view) = body[(off + 4)..].parse::<u32>() {
-                            Message::HosttargetEnd {
-                                host: &body[1..off],
-                                view: Some(view),
-                            }
-                        } else {
-                            return Err(Error::new(
-                                ErrorKind::Other,
-                                "Malformed HOSTTARGET command.",
-                            ));
-                        }
-                    } else {
-                        Message::HosttargetEnd {
-                            host: &body[1..off],
-                            view: None,
-                        }
-                    }
-                } else {
-                    let off = body.find(" :").ok_or_else(|| {
-                        Error::new(ErrorKind::Other, "Malformed HOSTTARGET command.")
-                    })?;
-
-                    if body.len() < off + 3 {
-                        return Err(Error::new(
-                            ErrorKind::Other,
-                            "Malformed HOSTTARGET command.",
-                        ));
+                let (host, rest) = Self::split_trailing(body, off_body, "HOSTTARGET")?;
+                let host = Self::parse_channel(host, off_body, "HOSTTARGET")?;
+
+                let (chan, view) = match rest.find(' ') {
+                    Some(idx) => (&rest[..idx], Some(&rest[(idx + 1)..])),
+                    None => (rest, None),
+                };
+
+                let view = match view {
+                    Some(view) => {
+                        Some(
+                            view.parse::<u32>()
+                                .map_err(|_| ParseError::InvalidViewers {
+                                    offset: off_body + body.len() - view.len(),
+                                    command: "HOSTTARGET",
+                                })?,
+                        )
                     }
+                    None => None,
+                };
 
-                    let host = &body[1..off];
-                    let body = &body[(off + 2)..];
-
-                    if let Some(off) = body.find(' ') {
-                        if body.len() > off + 2 {
-                            if let Ok(view) = body[(off + 1)..].parse::<u32>() {
-                                return Ok(Message::HosttargetStart {
-                                    host,
-                                    chan: &body[..off],
-                                    view: Some(view),
-                                });
-                            } else {
-                                return Err(Error::new(
-                                    ErrorKind::Other,
-                                    "Malformed HOSTTARGET command.",
-                                ));
-                            }
-                        } else {
-                            Message::HosttargetStart {
-                                host,
-                                chan: body,
-                                view: None,
-                            }
-                        }
-                    } else {
-                        Message::HosttargetStart {
-                            host,
-                            chan: body,
-                            view: None,
-                        }
+                match chan {
+                    "-" => Message::HosttargetEnd { prefix, host, view },
+                    "" => {
+                        return Err(ParseError::MissingTrailing {
+                            offset: off_body + body.len() - rest.len(),
+                            command: "HOSTTARGET",
+                        })
                     }
+                    _ => Message::HosttargetStart {
+                        prefix,
+                        host,
+                        chan,
+                        view,
+                    },
                 }
             }
             "NOTICE" => {
-                let off = body
-                    .find(" :")
-                    .ok_or_else(|| Error::new(ErrorKind::Other, "Malformed NOTICE command."))?;
+                let (chan, msg) = Self::split_trailing(body, off_body, "NOTICE")?;
+
+                let chan = if chan == "*" {
+                    None
+                } else {
+                    Some(Self::parse_channel(chan, off_body, "NOTICE")?)
+                };
 
                 Message::Notice {
                     tags,
-                    chan: &body[1..off],
-                    msg: &body[(off + 2)..],
+                    prefix,
+                    chan,
+                    msg,
                 }
             }
-            "RECONNECT" => Message::Reconnect,
+            "RECONNECT" => Message::Reconnect { prefix },
             "ROOMSTATE" => Message::Roomstate {
                 tags,
-                chan: &body[1..],
+                prefix,
+                chan: Self::parse_channel(body, off_body, "ROOMSTATE")?,
             },
             "USERNOTICE" => {
-                let off = body
-                    .find(" :")
-                    .ok_or_else(|| Error::new(ErrorKind::Other, "Malformed USERNOTICE command."))?;
+                let (chan, msg) = match Self::split_trailing(body, off_body, "USERNOTICE") {
+                    Ok((chan, msg)) => (chan, Some(msg)),
+                    Err(_) => (body, None),
+                };
 
                 Message::Usernotice {
                     tags,
-                    chan: &body[1..off],
-                    msg: &body[(off + 2)..],
+                    prefix,
+                    chan: Self::parse_channel(chan, off_body, "USERNOTICE")?,
+                    msg,
                 }
             }
             "USERSTATE" => Message::Userstate {
                 tags,
-                chan: &body[1..],
+                prefix,
+                chan: Self::parse_channel(body, off_body, "USERSTATE")?,
+            },
+            "GLOBALUSERSTATE" => Message::GlobalUserstate { tags, prefix },
+            _ if cmd.len() == 3 && cmd.bytes().all(|b| b.is_ascii_digit()) => Message::Numeric {
+                prefix,
+                code: cmd.parse().unwrap_or_default(),
+                params: body,
             },
-            "GLOBALUSERSTATE" => Message::GlobalUserstate { tags },
             _ => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "Parsing message command failed.",
-                ))
+                return Err(ParseError::UnknownCommand {
+                    offset: off,
+                    command: cmd.to_owned(),
+                })
             }
         })
     }
 
+    /// Returns the PONG message answering a [`Message::Ping`], echoing its token, otherwise None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let ping = Message::parse("PING :tmi.twitch.tv").unwrap();
+    /// let pong = ping.reply().unwrap();
+    /// # assert_eq!(pong.unparse().unwrap(), "PONG :tmi.twitch.tv");
+    /// ```
+    pub fn reply(&self) -> Option<Message<'a>> {
+        match self {
+            Message::Ping { token, .. } => Some(Message::Pong {
+                prefix: None,
+                token: *token,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the tags of the message, if any.
+    pub fn tags(&self) -> Option<&Tags<'a>> {
+        match self {
+            Message::Privmsg { tags, .. }
+            | Message::Whisper { tags, .. }
+            | Message::Clearchat { tags, .. }
+            | Message::Clearmsg { tags, .. }
+            | Message::Notice { tags, .. }
+            | Message::Roomstate { tags, .. }
+            | Message::Usernotice { tags, .. }
+            | Message::Userstate { tags, .. }
+            | Message::GlobalUserstate { tags, .. } => tags.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the source prefix of the message, if any.
+    pub fn prefix(&self) -> Option<&Prefix<'a>> {
+        match self {
+            Message::Ping { prefix, .. }
+            | Message::Pong { prefix, .. }
+            | Message::CapReq { prefix, .. }
+            | Message::CapAck { prefix, .. }
+            | Message::CapNak { prefix, .. }
+            | Message::CapLs { prefix, .. }
+            | Message::CapList { prefix, .. }
+            | Message::CapEnd { prefix }
+            | Message::Pass { prefix, .. }
+            | Message::Nick { prefix, .. }
+            | Message::Join { prefix, .. }
+            | Message::Part { prefix, .. }
+            | Message::Privmsg { prefix, .. }
+            | Message::Clearchat { prefix, .. }
+            | Message::Clearmsg { prefix, .. }
+            | Message::HosttargetStart { prefix, .. }
+            | Message::HosttargetEnd { prefix, .. }
+            | Message::Notice { prefix, .. }
+            | Message::Reconnect { prefix }
+            | Message::Roomstate { prefix, .. }
+            | Message::Usernotice { prefix, .. }
+            | Message::Userstate { prefix, .. }
+            | Message::Numeric { prefix, .. }
+            | Message::GlobalUserstate { prefix, .. } => prefix.as_ref(),
+            Message::Whisper { from, .. } => Some(from),
+        }
+    }
+
+    /// Returns the login of the user originating the message, taken from its prefix.
+    /// Used with the `twitch.tv/membership` capability to know who joined or left a channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let msg = Message::parse(":ronni!ronni@ronni.tmi.twitch.tv JOIN #dallas").unwrap();
+    /// # assert_eq!(msg.login(), Some("ronni"));
+    /// ```
+    pub fn login(&self) -> Option<&'a str> {
+        self.prefix().and_then(Prefix::nick)
+    }
+
     /// Unparses a Message and returns a newly allocated [`String`] if successful, otherwise an [`std::io::Error`].
     ///
+    /// Tags and prefix are written back as parsed, so that unparsing a parsed message
+    /// returns the original line, without surrounding whitespace, except that `PING` and `PONG`
+    /// tokens are always written as trailing parameters and `HOSTTARGET` viewers as plain numbers.
+    ///
     /// # Examples
     ///
     /// ```
     /// # use tmi_parser::*;
-    /// let msg = Message::Part { chan: "dallas" };
+    /// let msg = Message::Part { prefix: None, chan: "dallas" };
     /// let s = msg.unparse();
     /// ```
-    pub fn unparse(&self) -> Result<String> {
-        const ENDPOINT: &str = "tmi.twitch.tv";
-
-        Ok(match self {
-            Message::Ping => format!("PING {}", ENDPOINT),
-            Message::Pong => format!("PONG {}", ENDPOINT),
-            Message::CapReq { req } => format!("CAP REQ {}", req),
-            Message::CapAck { req } => format!("{} CAP * ACK {}", ENDPOINT, req),
-            Message::Pass { pass } => format!("PASS {}", pass),
-            Message::Nick { nick } => format!("NICK {}", nick),
-            Message::Join { chan } => format!("JOIN #{}", chan),
-            Message::Part { chan } => format!("PART #{}", chan),
-            Message::Privmsg { tags, chan, msg } => {
-                if let Some(mut body) = Self::unparse_tags(tags) {
-                    write!(body, "PRIVMSG #{} :{}", chan, msg).unwrap();
-                    body
-                } else {
-                    format!("PRIVMSG #{} :{}", chan, msg)
-                }
-            }
-            Message::Clearchat { tags, chan, usr } => {
-                let mut body = match Self::unparse_tags(tags) {
-                    Some(body) => body,
-                    _ => String::new(),
-                };
+    pub fn unparse(&self) -> io::Result<String> {
+        let mut raw = String::new();
+        self.write_to(&mut raw)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        Ok(raw)
+    }
 
-                if let Some(usr) = usr {
-                    write!(body, ":{} CLEARCHAT #{} :{}", ENDPOINT, chan, usr).unwrap();
-                } else {
-                    write!(body, ":{} CLEARCHAT #{}", ENDPOINT, chan).unwrap();
-                }
+    /// Unparses a Message like [`Message::unparse`], terminating the line with `\r\n`
+    /// as required when sending it over the connection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let msg = Message::CapReq { prefix: None, req: "twitch.tv/tags" };
+    /// # assert_eq!(msg.unparse_line().unwrap(), "CAP REQ :twitch.tv/tags\r\n");
+    /// ```
+    pub fn unparse_line(&self) -> io::Result<String> {
+        let mut raw = self.unparse()?;
+        raw.push_str("\r\n");
+        Ok(raw)
+    }
 
-                body
-            }
-            Message::Clearmsg { tags, chan, msg } => {
-                if let Some(mut body) = Self::unparse_tags(tags) {
-                    write!(body, ":{} CLEARMSG #{} :{}", ENDPOINT, chan, msg).unwrap();
-                    body
-                } else {
-                    format!(":{} CLEARMSG #{} :{}", ENDPOINT, chan, msg)
-                }
-            }
-            Message::HosttargetStart { host, chan, view } => {
-                if let Some(view) = view {
-                    format!(":{} HOSTTARGET #{} :{} {}", ENDPOINT, host, chan, view)
-                } else {
-                    format!(":{} HOSTTARGET #{} :{}", ENDPOINT, host, chan)
-                }
-            }
-            Message::HosttargetEnd { host, view } => {
-                if let Some(view) = view {
-                    format!(":{} HOSTTARGET #{} :- {}", ENDPOINT, host, view)
-                } else {
-                    format!(":{} HOSTTARGET #{} :-", ENDPOINT, host)
-                }
-            }
-            Message::Notice { tags, chan, msg } => {
-                if let Some(mut body) = Self::unparse_tags(tags) {
-                    write!(body, ":{} NOTICE #{} :{}", ENDPOINT, chan, msg).unwrap();
-                    body
-                } else {
-                    format!(":{} NOTICE #{} :{}", ENDPOINT, chan, msg)
-                }
-            }
-            Message::Reconnect => String::from("RECONNECT"),
-            Message::Roomstate { tags, chan } => {
-                if let Some(mut body) = Self::unparse_tags(tags) {
-                    write!(body, ":{} ROOMSTATE #{}", ENDPOINT, chan).unwrap();
-                    body
-                } else {
-                    format!(":{} ROOMSTATE #{}", ENDPOINT, chan)
-                }
-            }
-            Message::Usernotice { tags, chan, msg } => {
-                if let Some(mut body) = Self::unparse_tags(tags) {
-                    write!(body, ":{} USERNOTICE #{} :{}", ENDPOINT, chan, msg).unwrap();
-                    body
-                } else {
-                    format!(":{} USERNOTICE #{} :{}", ENDPOINT, chan, msg)
-                }
-            }
-            Message::Userstate { tags, chan } => {
-                if let Some(mut body) = Self::unparse_tags(tags) {
-                    write!(body, ":{} USERSTATE #{}", ENDPOINT, chan).unwrap();
-                    body
-                } else {
-                    format!(":{} USERSTATE #{}", ENDPOINT, chan)
-                }
-            }
-            Message::GlobalUserstate { tags } => {
-                if let Some(mut body) = Self::unparse_tags(tags) {
-                    write!(body, ":{} GLOBALUSERSTATE", ENDPOINT).unwrap();
-                    body
-                } else {
-                    format!(":{} GLOBALUSERSTATE", ENDPOINT)
-                }
-            }
-        })
+    /// Unparses a Message into the given [`fmt::Write`] sink, without allocating.
+    /// See [`Message::unparse`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let mut buf = String::with_capacity(512);
+    /// Message::Join { prefix: None, chan: "dallas" }.write_to(&mut buf).unwrap();
+    /// # assert_eq!(buf, "JOIN #dallas");
+    /// ```
+    pub fn write_to<W: fmt::Write>(&self, mut w: W) -> fmt::Result {
+        Self::unparse_tags(&mut w, self.tags(), |_| true)?;
+        Self::unparse_prefix(&mut w, self.prefix())?;
+        self.unparse_command(&mut w)
     }
 
-    /// Helper function for unparsing message tags.
-    /// [`Tags`] doesn't store the original order of items!
-    fn unparse_tags(tags: &Option<Tags<'a>>) -> Option<String> {
-        if let Some(tags) = tags {
-            if !tags.is_empty() {
-                let mut raw = String::from("@");
-
-                for (key, val) in tags {
-                    raw.push_str(key);
-                    raw.push('=');
-                    raw.push_str(&format!("{}", val));
-                    raw.push(';');
-                }
+    /// Helper function for unparsing the command and its parameters, after tags and prefix.
+    pub(crate) fn unparse_command<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        match self {
+            Message::Ping {
+                token: Some(token), ..
+            } => write!(w, "PING :{}", token),
+            Message::Ping { token: None, .. } => write!(w, "PING"),
+            Message::Pong {
+                token: Some(token), ..
+            } => write!(w, "PONG :{}", token),
+            Message::Pong { token: None, .. } => write!(w, "PONG"),
+            Message::CapReq { req, .. } => write!(w, "CAP REQ :{}", req),
+            Message::CapAck { req, .. } => write!(w, "CAP * ACK :{}", req),
+            Message::CapNak { req, .. } => write!(w, "CAP * NAK :{}", req),
+            Message::CapLs {
+                caps: Some(caps),
+                more,
+                ..
+            } => write!(w, "CAP * LS {}:{}", if *more { "* " } else { "" }, caps),
+            Message::CapLs {
+                version: Some(ver),
+                caps: None,
+                ..
+            } => write!(w, "CAP LS {}", ver),
+            Message::CapLs { caps: None, .. } => write!(w, "CAP LS"),
+            Message::CapList {
+                caps: Some(caps), ..
+            } => write!(w, "CAP * LIST :{}", caps),
+            Message::CapList { caps: None, .. } => write!(w, "CAP LIST"),
+            Message::CapEnd { .. } => write!(w, "CAP END"),
+            Message::Pass { pass: "", .. } => write!(w, "PASS"),
+            Message::Pass { pass, .. } => write!(w, "PASS {}", pass),
+            Message::Nick { nick: "", .. } => write!(w, "NICK"),
+            Message::Nick { nick, .. } => write!(w, "NICK {}", nick),
+            Message::Join { chan, .. } => write!(w, "JOIN #{}", chan),
+            Message::Part { chan, .. } => write!(w, "PART #{}", chan),
+            Message::Privmsg { chan, msg, .. } => write!(w, "PRIVMSG #{} :{}", chan, msg),
+            Message::Whisper { to, msg, .. } => write!(w, "WHISPER {} :{}", to, msg),
+            Message::Clearchat {
+                chan,
+                usr: Some(usr),
+                ..
+            } => write!(w, "CLEARCHAT #{} :{}", chan, usr),
+            Message::Clearchat {
+                chan, usr: None, ..
+            } => write!(w, "CLEARCHAT #{}", chan),
+            Message::Clearmsg { chan, msg, .. } => write!(w, "CLEARMSG #{} :{}", chan, msg),
+            Message::HosttargetStart {
+                host,
+                chan,
+                view: Some(view),
+                ..
+            } => write!(w, "HOSTTARGET #{} :{} {}", host, chan, view),
+            Message::HosttargetStart {
+                host,
+                chan,
+                view: None,
+                ..
+            } => write!(w, "HOSTTARGET #{} :{}", host, chan),
+            Message::HosttargetEnd {
+                host,
+                view: Some(view),
+                ..
+            } => write!(w, "HOSTTARGET #{} :- {}", host, view),
+            Message::HosttargetEnd {
+                host, view: None, ..
+            } => write!(w, "HOSTTARGET #{} :-", host),
+            Message::Notice {
+                chan: Some(chan),
+                msg,
+                ..
+            } => write!(w, "NOTICE #{} :{}", chan, msg),
+            Message::Notice {
+                chan: None, msg, ..
+            } => write!(w, "NOTICE * :{}", msg),
+            Message::Reconnect { .. } => write!(w, "RECONNECT"),
+            Message::Roomstate { chan, .. } => write!(w, "ROOMSTATE #{}", chan),
+            Message::Usernotice {
+                chan,
+                msg: Some(msg),
+                ..
+            } => write!(w, "USERNOTICE #{} :{}", chan, msg),
+            Message::Usernotice {
+                chan, msg: None, ..
+            } => write!(w, "USERNOTICE #{}", chan),
+            Message::Userstate { chan, .. } => write!(w, "USERSTATE #{}", chan),
+            Message::Numeric {
+                code, params: "", ..
+            } => write!(w, "{:03}", code),
+            Message::Numeric { code, params, .. } => write!(w, "{:03} {}", code, params),
+            Message::GlobalUserstate { .. } => write!(w, "GLOBALUSERSTATE"),
+        }
+    }
 
-                raw.pop();
-                raw.push(' ');
+    /// Unparses a Message into the given [`io::Write`] sink, such as a socket buffer.
+    /// See [`Message::unparse`].
+    pub fn write_io<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        write!(w, "{}", self)
+    }
 
-                Some(raw)
-            } else {
-                None
+    /// Helper function for unparsing the message tags whose key is kept.
+    pub(crate) fn unparse_tags<W: fmt::Write>(
+        w: &mut W,
+        tags: Option<&Tags>,
+        keep: fn(&str) -> bool,
+    ) -> fmt::Result {
+        match tags {
+            Some(tags) if tags.iter().any(|(key, _)| keep(key)) => {
+                tags.write_to(w, keep)?;
+                w.write_char(' ')
             }
-        } else {
-            None
+            _ => Ok(()),
+        }
+    }
+
+    /// Helper function for unparsing the message prefix.
+    pub(crate) fn unparse_prefix<W: fmt::Write>(w: &mut W, prefix: Option<&Prefix>) -> fmt::Result {
+        match prefix {
+            Some(prefix) => write!(w, ":{} ", prefix),
+            None => Ok(()),
         }
     }
 }
+
+impl<'a> fmt::Display for Message<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
@@ -0,0 +1,136 @@
+//! Kinds of NOTICE messages.
+
+use crate::typed_tags::string;
+use crate::Message;
+
+/// Possible kinds of NOTICE messages, decoded from the `msg-id` tag.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let msg = Message::parse("@msg-id=msg_ratelimit :tmi.twitch.tv NOTICE #dallas \
+///                           :Your message was not sent because you are sending messages too quickly.").unwrap();
+/// # assert_eq!(msg.notice_kind(), Some(NoticeKind::MsgRatelimit));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoticeKind<'a> {
+    /// Represents a message sent too quickly, `msg_ratelimit`.
+    MsgRatelimit,
+    /// Represents a message sent by a banned user, `msg_banned`.
+    MsgBanned,
+    /// Represents a message sent by a timed out user, `msg_timedout`.
+    MsgTimedout,
+    /// Represents a message sent to a suspended channel, `msg_channel_suspended`.
+    MsgChannelSuspended,
+    /// Represents a message identical to the previous one, `msg_duplicate`.
+    MsgDuplicate,
+    /// Represents a message sent too quickly in slow mode, `msg_slowmode`.
+    MsgSlowmode,
+    /// Represents a message sent by a non subscriber in subscribers-only mode, `msg_subsonly`.
+    MsgSubsonly,
+    /// Represents a message sent by a non follower in followers-only mode, `msg_followersonly`.
+    MsgFollowersonly,
+    /// Represents a message with text in emote-only mode, `msg_emoteonly`.
+    MsgEmoteonly,
+    /// Represents a non unique message in r9k mode, `msg_r9k`.
+    MsgR9k,
+    /// Represents a message sent without a verified email, `msg_verified_email`.
+    MsgVerifiedEmail,
+    /// Represents a message sent without a verified phone number, `msg_requires_verified_phone_number`.
+    MsgRequiresVerifiedPhoneNumber,
+    /// Represents slow mode being enabled, `slow_on`.
+    SlowOn,
+    /// Represents slow mode being disabled, `slow_off`.
+    SlowOff,
+    /// Represents emote-only mode being enabled, `emote_only_on`.
+    EmoteOnlyOn,
+    /// Represents emote-only mode being disabled, `emote_only_off`.
+    EmoteOnlyOff,
+    /// Represents followers-only mode being enabled, `followers_on` or `followers_on_zero`.
+    FollowersOn,
+    /// Represents followers-only mode being disabled, `followers_off`.
+    FollowersOff,
+    /// Represents subscribers-only mode being enabled, `subs_on`.
+    SubsOn,
+    /// Represents subscribers-only mode being disabled, `subs_off`.
+    SubsOff,
+    /// Represents r9k mode being enabled, `r9k_on`.
+    R9kOn,
+    /// Represents r9k mode being disabled, `r9k_off`.
+    R9kOff,
+    /// Represents a message deleted by a moderator, `delete_message_success`.
+    DeleteMessageSuccess,
+    /// Represents a user banned by a moderator, `ban_success`.
+    BanSuccess,
+    /// Represents a user timed out by a moderator, `timeout_success`.
+    TimeoutSuccess,
+    /// Represents an unrecognized chat command, `unrecognized_cmd`.
+    UnrecognizedCmd,
+    /// Represents a failed login, sent as a global notice without `msg-id`.
+    LoginFailed,
+    /// Represents any other `msg-id`.
+    Unknown(&'a str),
+}
+
+impl<'a> NoticeKind<'a> {
+    /// Returns a NoticeKind variant based on the given `msg-id`.
+    pub fn new(id: &'a str) -> NoticeKind<'a> {
+        match id {
+            "msg_ratelimit" => NoticeKind::MsgRatelimit,
+            "msg_banned" => NoticeKind::MsgBanned,
+            "msg_timedout" => NoticeKind::MsgTimedout,
+            "msg_channel_suspended" => NoticeKind::MsgChannelSuspended,
+            "msg_duplicate" => NoticeKind::MsgDuplicate,
+            "msg_slowmode" => NoticeKind::MsgSlowmode,
+            "msg_subsonly" => NoticeKind::MsgSubsonly,
+            "msg_followersonly" => NoticeKind::MsgFollowersonly,
+            "msg_emoteonly" => NoticeKind::MsgEmoteonly,
+            "msg_r9k" => NoticeKind::MsgR9k,
+            "msg_verified_email" => NoticeKind::MsgVerifiedEmail,
+            "msg_requires_verified_phone_number" => NoticeKind::MsgRequiresVerifiedPhoneNumber,
+            "slow_on" => NoticeKind::SlowOn,
+            "slow_off" => NoticeKind::SlowOff,
+            "emote_only_on" => NoticeKind::EmoteOnlyOn,
+            "emote_only_off" => NoticeKind::EmoteOnlyOff,
+            "followers_on" | "followers_on_zero" => NoticeKind::FollowersOn,
+            "followers_off" => NoticeKind::FollowersOff,
+            "subs_on" => NoticeKind::SubsOn,
+            "subs_off" => NoticeKind::SubsOff,
+            "r9k_on" => NoticeKind::R9kOn,
+            "r9k_off" => NoticeKind::R9kOff,
+            "delete_message_success" => NoticeKind::DeleteMessageSuccess,
+            "ban_success" => NoticeKind::BanSuccess,
+            "timeout_success" => NoticeKind::TimeoutSuccess,
+            "unrecognized_cmd" => NoticeKind::UnrecognizedCmd,
+            _ => NoticeKind::Unknown(id),
+        }
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Returns the kind of a [`Message::Notice`], otherwise None.
+    ///
+    /// Global notices without `msg-id` about authentication are [`NoticeKind::LoginFailed`].
+    pub fn notice_kind(&self) -> Option<NoticeKind<'_>> {
+        match self {
+            Message::Notice {
+                tags, chan, msg, ..
+            } => match tags.as_ref().and_then(|tags| string(tags, "msg-id")) {
+                Some(id) => Some(NoticeKind::new(id)),
+                None if chan.is_none()
+                    && matches!(
+                        *msg,
+                        "Login authentication failed"
+                            | "Login unsuccessful"
+                            | "Improperly formatted auth"
+                    ) =>
+                {
+                    Some(NoticeKind::LoginFailed)
+                }
+                None => None,
+            },
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Numeric replies of TMI messages.
+
+use crate::Message;
+use std::str::SplitWhitespace;
+
+/// `<user> :Welcome, GLHF!`
+pub const RPL_WELCOME: u16 = 1;
+/// `<user> :Your host is tmi.twitch.tv`
+pub const RPL_YOURHOST: u16 = 2;
+/// `<user> :This server is rather new`
+pub const RPL_CREATED: u16 = 3;
+/// `<user> :-`
+pub const RPL_MYINFO: u16 = 4;
+/// `<user> = #<channel> :<user> <user> ...`
+pub const RPL_NAMREPLY: u16 = 353;
+/// `<user> #<channel> :End of /NAMES list`
+pub const RPL_ENDOFNAMES: u16 = 366;
+/// `<user> :<line>`
+pub const RPL_MOTD: u16 = 372;
+/// `<user> :-`
+pub const RPL_MOTDSTART: u16 = 375;
+/// `<user> :>`
+pub const RPL_ENDOFMOTD: u16 = 376;
+/// `<user> <command> :Unknown command`
+pub const ERR_UNKNOWNCOMMAND: u16 = 421;
+
+/// Iterator over the parameters of a numeric reply.
+/// The trailing parameter is yielded without its leading `:`.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let mut params = Params::new("ronni #dallas :End of /NAMES list");
+/// # assert_eq!(params.next(), Some("ronni"));
+/// # assert_eq!(params.next(), Some("#dallas"));
+/// # assert_eq!(params.next(), Some("End of /NAMES list"));
+/// # assert_eq!(params.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Params<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Params<'a> {
+    /// Returns an iterator over the given raw parameters.
+    pub fn new(raw: &'a str) -> Params<'a> {
+        Params { rest: raw }
+    }
+}
+
+impl<'a> Iterator for Params<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest.trim_start_matches(' ');
+
+        if rest.is_empty() {
+            self.rest = rest;
+            None
+        } else if let Some(trailing) = rest.strip_prefix(':') {
+            self.rest = "";
+            Some(trailing)
+        } else if let Some(idx) = rest.find(' ') {
+            self.rest = &rest[(idx + 1)..];
+            Some(&rest[..idx])
+        } else {
+            self.rest = "";
+            Some(rest)
+        }
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Returns the parameters of a [`Message::Numeric`], otherwise None.
+    pub fn params(&self) -> Option<Params<'a>> {
+        match self {
+            Message::Numeric { params, .. } => Some(Params::new(params)),
+            _ => None,
+        }
+    }
+
+    /// Returns the users listed by a [`RPL_NAMREPLY`] numeric reply, otherwise None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let msg = Message::parse(":ronni.tmi.twitch.tv 353 ronni = #dallas :ronni fred wilma").unwrap();
+    /// let names = msg.names().unwrap().collect::<Vec<_>>();
+    /// # assert_eq!(names, ["ronni", "fred", "wilma"]);
+    /// ```
+    pub fn names(&self) -> Option<SplitWhitespace<'a>> {
+        match self {
+            Message::Numeric {
+                code: RPL_NAMREPLY,
+                params,
+                ..
+            } => Params::new(params).last().map(str::split_whitespace),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Source prefix of TMI messages.
+
+use std::fmt;
+
+/// Source of a message, as found in the optional `:<prefix>` part of the line.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let prefix = Prefix::parse("ronni!ronni@ronni.tmi.twitch.tv");
+/// # assert_eq!(prefix.and_then(|p| p.nick()), Some("ronni"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prefix<'a> {
+    /// Represents a server name prefix.
+    /// `:<host>`
+    Server(&'a str),
+    /// Represents a user prefix.
+    /// `:<nick>[!<user>][@<host>]`
+    User {
+        nick: &'a str,
+        user: Option<&'a str>,
+        host: Option<&'a str>,
+    },
+}
+
+impl<'a> Prefix<'a> {
+    /// Parses a prefix without its leading `:` and returns None if it is malformed.
+    ///
+    /// A prefix without `!` or `@` is treated as a server name if it contains a `.`,
+    /// otherwise as a bare nickname.
+    pub fn parse(raw: &'a str) -> Option<Prefix<'a>> {
+        if raw.is_empty() || raw.contains(' ') {
+            return None;
+        }
+
+        let (rest, host) = match raw.find('@') {
+            Some(off) => (&raw[..off], Some(&raw[(off + 1)..])),
+            None => (raw, None),
+        };
+
+        let (nick, user) = match rest.find('!') {
+            Some(off) => (&rest[..off], Some(&rest[(off + 1)..])),
+            None => (rest, None),
+        };
+
+        if nick.is_empty() || user == Some("") || host == Some("") {
+            None
+        } else if user.is_none() && host.is_none() && nick.contains('.') {
+            Some(Prefix::Server(nick))
+        } else {
+            Some(Prefix::User { nick, user, host })
+        }
+    }
+
+    /// Returns the nickname of a user prefix, or None for a server prefix.
+    pub fn nick(&self) -> Option<&'a str> {
+        match self {
+            Prefix::Server(_) => None,
+            Prefix::User { nick, .. } => Some(nick),
+        }
+    }
+
+    /// Returns the host of the prefix, which is the whole prefix for a server.
+    pub fn host(&self) -> Option<&'a str> {
+        match self {
+            Prefix::Server(host) => Some(host),
+            Prefix::User { host, .. } => *host,
+        }
+    }
+}
+
+impl<'a> fmt::Display for Prefix<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Prefix::Server(host) => write!(f, "{}", host),
+            Prefix::User { nick, user, host } => {
+                write!(f, "{}", nick)?;
+
+                if let Some(user) = user {
+                    write!(f, "!{}", user)?;
+                }
+
+                if let Some(host) = host {
+                    write!(f, "@{}", host)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Settings of chat rooms.
+
+use crate::{Message, RoomstateTags, Tags};
+
+/// Settings of a chat room, as carried by ROOMSTATE messages.
+///
+/// A ROOMSTATE sent on join carries every setting, while later ones only carry
+/// the changed settings, which are applied to the known state with [`RoomSettings::merge`].
+/// Settings are None when not carried by the message.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let join = Message::parse("@emote-only=0;followers-only=-1;r9k=0;room-id=1337;slow=0;subs-only=0 \
+///                            :tmi.twitch.tv ROOMSTATE #dallas").unwrap();
+/// let update = Message::parse("@room-id=1337;slow=10 :tmi.twitch.tv ROOMSTATE #dallas").unwrap();
+///
+/// let mut settings = join.room_settings().unwrap();
+/// settings.merge(&update.room_settings().unwrap());
+/// # assert_eq!(settings.slow, Some(10));
+/// # assert_eq!(settings.emote_only, Some(false));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoomSettings {
+    /// Whether only emotes are allowed, from `emote-only`.
+    pub emote_only: Option<bool>,
+    /// Minimum follow time in minutes to chat, where -1 means disabled, from `followers-only`.
+    pub followers_only: Option<i64>,
+    /// Whether unique chat mode is enabled, from `r9k`.
+    pub r9k: Option<bool>,
+    /// Delay between messages in seconds, where 0 means disabled, from `slow`.
+    pub slow: Option<u32>,
+    /// Whether only subscribers can chat, from `subs-only`.
+    pub subs_only: Option<bool>,
+    /// Identifier of the channel, from `room-id`.
+    pub room_id: Option<String>,
+}
+
+impl RoomSettings {
+    /// Returns the settings carried by the given tags of a ROOMSTATE message.
+    pub fn new(tags: &Tags) -> RoomSettings {
+        let view = RoomstateTags::new(tags);
+
+        RoomSettings {
+            emote_only: view.emote_only(),
+            followers_only: view.followers_only(),
+            r9k: view.r9k(),
+            slow: view.slow(),
+            subs_only: view.subs_only(),
+            room_id: view.room_id().map(String::from),
+        }
+    }
+
+    /// Applies the settings present in the given update, keeping the others.
+    pub fn merge(&mut self, update: &RoomSettings) {
+        if update.emote_only.is_some() {
+            self.emote_only = update.emote_only;
+        }
+        if update.followers_only.is_some() {
+            self.followers_only = update.followers_only;
+        }
+        if update.r9k.is_some() {
+            self.r9k = update.r9k;
+        }
+        if update.slow.is_some() {
+            self.slow = update.slow;
+        }
+        if update.subs_only.is_some() {
+            self.subs_only = update.subs_only;
+        }
+        if update.room_id.is_some() {
+            self.room_id.clone_from(&update.room_id);
+        }
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Returns the settings carried by a [`Message::Roomstate`], otherwise None.
+    ///
+    /// A ROOMSTATE without tags carries no settings.
+    pub fn room_settings(&self) -> Option<RoomSettings> {
+        match self {
+            Message::Roomstate { tags, .. } => Some(
+                tags.as_ref()
+                    .map(|tags| RoomSettings::new(tags))
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        }
+    }
+}
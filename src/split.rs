@@ -0,0 +1,228 @@
+//! Splitting of long chat messages.
+
+use crate::encode::Checker;
+use crate::marks::MARKS;
+use crate::{Message, MAX_LINE_LEN, MAX_MESSAGE_CHARS};
+use std::cmp::Ordering;
+
+/// Helper function for checking if a char extends the previous grapheme,
+/// such as combining and spacing marks, variation selectors, emoji modifiers and joiners.
+fn is_extend(ch: char) -> bool {
+    let code = ch as u32;
+
+    matches!(code, 0x200C..=0x200D | 0x1F3FB..=0x1F3FF | 0xE0020..=0xE007F)
+        || MARKS
+            .binary_search_by(|&(start, end)| {
+                if end < code {
+                    Ordering::Less
+                } else if start > code {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok()
+}
+
+/// Possible kinds of Hangul jamo and syllables.
+#[derive(Clone, Copy)]
+enum Jamo {
+    /// Leading consonant.
+    Leading,
+    /// Vowel.
+    Vowel,
+    /// Trailing consonant.
+    Trailing,
+    /// Syllable without trailing consonant.
+    Open,
+    /// Syllable with trailing consonant.
+    Closed,
+}
+
+/// Helper function for getting the Hangul jamo kind of a char, if any.
+fn jamo(ch: char) -> Option<Jamo> {
+    match ch as u32 {
+        0x1100..=0x115F | 0xA960..=0xA97C => Some(Jamo::Leading),
+        0x1160..=0x11A7 | 0xD7B0..=0xD7C6 => Some(Jamo::Vowel),
+        0x11A8..=0x11FF | 0xD7CB..=0xD7FB => Some(Jamo::Trailing),
+        code @ 0xAC00..=0xD7A3 if (code - 0xAC00) % 28 == 0 => Some(Jamo::Open),
+        0xAC00..=0xD7A3 => Some(Jamo::Closed),
+        _ => None,
+    }
+}
+
+/// Helper function for checking if two chars are joined into a Hangul syllable.
+fn is_syllable(prev: char, ch: char) -> bool {
+    use Jamo::*;
+
+    matches!(
+        (jamo(prev), jamo(ch)),
+        (Some(Leading), Some(Leading | Vowel | Open | Closed))
+            | (Some(Open | Vowel), Some(Vowel | Trailing))
+            | (Some(Closed | Trailing), Some(Trailing))
+    )
+}
+
+/// Helper function for checking if a char is a regional indicator, paired into flags.
+fn is_regional(ch: char) -> bool {
+    matches!(ch as u32, 0x1F1E6..=0x1F1FF)
+}
+
+/// Returns the byte offsets of the approximate grapheme boundaries of the given string,
+/// excluding the start and including the end.
+///
+/// Chars are not split from the combining marks, variation selectors and emoji modifiers
+/// that follow them, emoji joined by a zero width joiner and Hangul jamo are kept together,
+/// and regional indicators are kept in pairs.
+fn boundaries(text: &str) -> Vec<usize> {
+    let mut list = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut regional = 0;
+
+    for (idx, ch) in text.char_indices() {
+        let joined = match prev {
+            None => true,
+            Some(prev) => {
+                is_extend(ch)
+                    || is_syllable(prev, ch)
+                    || prev == '\u{200D}'
+                    || (is_regional(ch) && regional % 2 == 1)
+            }
+        };
+
+        if !joined {
+            list.push(idx);
+        }
+
+        regional = if is_regional(ch) { regional + 1 } else { 0 };
+        prev = Some(ch);
+    }
+
+    if !text.is_empty() {
+        list.push(text.len());
+    }
+
+    list
+}
+
+/// Splits the given text into chunks of at most the given chars and bytes,
+/// preferably at whitespace, otherwise at grapheme boundaries.
+fn chunks(text: &str, max_chars: usize, max_bytes: usize) -> Vec<&str> {
+    let mut list = Vec::new();
+    let mut rest = text.trim();
+
+    while rest.len() > max_bytes || rest.chars().nth(max_chars).is_some() {
+        // Only the text up to the first char past the byte limit is scanned,
+        // so that each pass takes time proportional to the limits.
+        let end = rest
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .find(|&idx| idx > max_bytes)
+            .unwrap_or(rest.len());
+
+        // Last grapheme boundary within the limits.
+        let mut cut = None;
+        let mut chars = 0;
+        let mut last = 0;
+
+        for idx in boundaries(&rest[..end]) {
+            chars += rest[last..idx].chars().count();
+
+            if idx > max_bytes || chars > max_chars {
+                break;
+            }
+
+            cut = Some(idx);
+            last = idx;
+        }
+
+        // A single grapheme over the limits is split between chars.
+        let cut = cut
+            .or_else(|| rest.char_indices().nth(1).map(|(idx, _)| idx))
+            .unwrap_or(rest.len());
+
+        let (chunk, next) = if rest[cut..].starts_with(char::is_whitespace) {
+            (&rest[..cut], &rest[cut..])
+        } else {
+            match rest[..cut].rfind(char::is_whitespace) {
+                Some(idx) if !rest[..idx].trim_end().is_empty() => (&rest[..idx], &rest[idx..]),
+                _ => (&rest[..cut], &rest[cut..]),
+            }
+        };
+
+        list.push(chunk.trim_end());
+        rest = next.trim_start();
+    }
+
+    if !rest.is_empty() || list.is_empty() {
+        list.push(rest);
+    }
+
+    list
+}
+
+impl<'a> Message<'a> {
+    /// Splits a [`Message::Privmsg`] whose message exceeds the Twitch chat or IRC line limits
+    /// into several ones, otherwise returns the message as is.
+    ///
+    /// Messages are split at whitespace when possible, otherwise at approximate grapheme
+    /// boundaries, so that emoji and accented letters are kept whole. A single grapheme
+    /// exceeding the limits on its own is split between chars.
+    /// The tags are kept on the first message only.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// let text = "Kappa ".repeat(100);
+    /// let msg = Message::Privmsg { tags: None, prefix: None, chan: "dallas", msg: &text };
+    /// let parts = msg.split_long();
+    /// # assert_eq!(parts.len(), 2);
+    /// # assert!(parts.iter().all(|part| part.encode(Role::Client).is_ok()));
+    /// ```
+    pub fn split_long(self) -> Vec<Message<'a>> {
+        let (mut tags, prefix, chan, msg) = match self {
+            Message::Privmsg {
+                tags,
+                prefix,
+                chan,
+                msg,
+            } => (tags, prefix, chan, msg),
+            _ => return vec![self],
+        };
+
+        // Bytes taken by the line without the message, including the trailing CR LF.
+        let mut line = Checker::default();
+
+        Message::unparse_prefix(&mut line, prefix.as_ref()).unwrap();
+        Message::Privmsg {
+            tags: None,
+            prefix: None,
+            chan,
+            msg: "",
+        }
+        .unparse_command(&mut line)
+        .unwrap();
+
+        let max_bytes = MAX_LINE_LEN.saturating_sub(line.len + 2).max(4);
+
+        if msg.len() <= max_bytes && msg.chars().count() <= MAX_MESSAGE_CHARS {
+            return vec![Message::Privmsg {
+                tags,
+                prefix,
+                chan,
+                msg,
+            }];
+        }
+
+        chunks(msg, MAX_MESSAGE_CHARS, max_bytes)
+            .into_iter()
+            .map(|msg| Message::Privmsg {
+                tags: tags.take(),
+                prefix,
+                chan,
+                msg,
+            })
+            .collect()
+    }
+}
@@ -1,84 +1,242 @@
 //! Tags for TMI messages
 
+use crate::Rgb;
+use std::borrow::Cow;
 use std::fmt;
-use std::hash::BuildHasherDefault;
-use std::{collections::HashMap, u32};
 
-/// [`Tags`] is type alias for a [`HashMap`] whose keys are [`&str`] and values [`TagValue`].
+/// [`Tags`] is an ordered list of message tags whose keys are [`&str`] and values [`TagValue`].
 /// Uses slice [`&str`] instead of owned [`String`] in order to avoid data duplication.
-/// Also uses a custom hasher which implements the 'Fnv 1a' hash function.
+///
+/// Tags keep the order in which they were parsed or inserted, along with the original
+/// text of parsed tags, so that unparsing a message writes them back unchanged.
+/// Since messages carry few tags, lookups are linear scans; if a key is repeated, the last one wins.
+///
+/// Two [`Tags`] are equal if they map the same keys to the same values, whatever the order.
 ///
 /// # Examples
 ///
 /// ```
 /// # use tmi_parser::*;
 /// let mut map = Tags::default();
-/// map.insert("hello", TagValue::String("world"));
-/// # assert_eq!(*map.get("hello").unwrap(), TagValue::String("world"));
+/// map.insert("hello", TagValue::String("world".into()));
+/// # assert_eq!(*map.get("hello").unwrap(), TagValue::String("world".into()));
 /// ````
-pub type Tags<'a> = HashMap<&'a str, TagValue<'a>, BuildHasherDefault<hash::TagsHasher>>;
+#[derive(Debug, Default)]
+pub struct Tags<'a> {
+    items: Vec<Tag<'a>>,
+}
 
-/// [`TagsHasher`] is an internal implementation of the 'Fnv 1a' hash function.
-/// It lives under the private module hash and was made only for improving [`Tags`] performance.
-/// Since [`Tags`] will contain few items in average, the faster the hash, the better the performance,
-mod hash {
-    use std::hash::Hasher;
+/// Single item of [`Tags`], with the original `<key>[=<value>]` text if parsed.
+#[derive(Debug)]
+struct Tag<'a> {
+    key: &'a str,
+    val: TagValue<'a>,
+    raw: Option<&'a str>,
+}
 
-    pub struct TagsHasher(u64);
+impl<'a> Tags<'a> {
+    /// Returns an empty list of tags.
+    pub fn new() -> Tags<'a> {
+        Tags::default()
+    }
 
-    impl Default for TagsHasher {
-        #[inline]
-        fn default() -> TagsHasher {
-            TagsHasher(14695981039346656037)
-        }
+    /// Appends a parsed tag, keeping its original text.
+    pub(crate) fn push_raw(&mut self, key: &'a str, val: TagValue<'a>, raw: &'a str) {
+        self.items.push(Tag {
+            key,
+            val,
+            raw: Some(raw),
+        });
     }
 
-    impl Hasher for TagsHasher {
-        #[inline]
-        fn finish(&self) -> u64 {
-            self.0
+    /// Inserts a tag and returns the previous value of the key, if any.
+    /// An existing key keeps its position, otherwise the tag is appended.
+    pub fn insert(&mut self, key: &'a str, val: TagValue<'a>) -> Option<TagValue<'a>> {
+        match self.items.iter_mut().rev().find(|tag| tag.key == key) {
+            Some(tag) => {
+                tag.raw = None;
+                Some(std::mem::replace(&mut tag.val, val))
+            }
+            None => {
+                self.items.push(Tag {
+                    key,
+                    val,
+                    raw: None,
+                });
+                None
+            }
         }
+    }
 
-        #[inline]
-        fn write(&mut self, bytes: &[u8]) {
-            let TagsHasher(mut hash) = *self;
+    /// Removes every tag with the given key and returns the last value, if any.
+    pub fn remove(&mut self, key: &str) -> Option<TagValue<'a>> {
+        let mut last = None;
+        let mut idx = 0;
 
-            for byte in bytes.iter() {
-                hash ^= *byte as u64;
-                hash = hash.wrapping_mul(1099511628211);
+        while idx < self.items.len() {
+            if self.items[idx].key == key {
+                last = Some(self.items.remove(idx).val);
+            } else {
+                idx += 1;
             }
+        }
+
+        last
+    }
+
+    /// Returns the value of the given key, if any.
+    pub fn get(&self, key: &str) -> Option<&TagValue<'a>> {
+        self.items
+            .iter()
+            .rev()
+            .find(|tag| tag.key == key)
+            .map(|tag| &tag.val)
+    }
+
+    /// Returns the original `<key>[=<value>]` text of the given key, if it was parsed and not replaced.
+    pub fn raw(&self, key: &str) -> Option<&'a str> {
+        self.items.iter().rev().find(|tag| tag.key == key)?.raw
+    }
+
+    /// Returns true if the given key is present.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns an iterator over the keys and values, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &TagValue<'a>)> {
+        self.items.iter().map(|tag| (tag.key, &tag.val))
+    }
+
+    /// Returns the number of tags.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if there are no tags.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
 
-            *self = TagsHasher(hash);
+    /// Writes the tags whose key is kept in the `@<key>=<value>;...` form, without the trailing space.
+    /// Parsed tags are written with their original text, the others are escaped.
+    pub(crate) fn write_to<W: fmt::Write>(&self, w: &mut W, keep: fn(&str) -> bool) -> fmt::Result {
+        let items = self.items.iter().filter(|tag| keep(tag.key));
+
+        for (idx, tag) in items.enumerate() {
+            w.write_char(if idx == 0 { '@' } else { ';' })?;
+
+            match (&tag.raw, &tag.val) {
+                (Some(raw), _) => w.write_str(raw)?,
+                (None, TagValue::String(val)) => {
+                    write!(w, "{}=", tag.key)?;
+                    escape_to(w, val)?;
+                }
+                (None, val) => write!(w, "{}={}", tag.key, val)?,
+            }
         }
+
+        Ok(())
+    }
+}
+
+impl<'a> PartialEq for Tags<'a> {
+    fn eq(&self, other: &Tags<'a>) -> bool {
+        self.iter().all(|(key, _)| self.get(key) == other.get(key))
+            && other.iter().all(|(key, _)| other.get(key) == self.get(key))
     }
 }
 
 /// Possible values of message tags.
 #[derive(Debug, PartialEq)]
 pub enum TagValue<'a> {
-    /// Represents a parsed sequence of numbers of type u32.
+    /// Represents a parsed `#RRGGBB` color.
     /// Used for the `color` tag.
-    Color(u32),
+    Color(Rgb),
     /// Represents a parsed sequence of numbers of type u32.
-    /// Used for generic numeric string.
+    /// Used for counts and durations, such as the `bits` tag.
     Number(u32),
+    /// Represents a parsed sequence of numbers of type i64.
+    /// Used for the `followers-only` tag, where "-1" means disabled.
+    Integer(i64),
     /// Represents a parsed sequence of numbers of type u64.
     /// Used for the `tmi-sent-ts` tag.
     Timestamp(u64),
     /// Boolean values represents literal "1" (true) or "0" (false).
-    ///
-    /// Note that a single digit number "1" or "0" may be represented
-    /// as a Boolean value instead of a Number value.
-    /// Type conversion should therefore be done by the user code.
     Boolean(bool),
-    /// Strings represent an unparsed string literal.
-    String(&'a str),
-    /// None represents literal empty string "".
+    /// Strings represent an unparsed string literal, with IRCv3 escapes already decoded.
+    /// The original slice is borrowed if it contained no escape sequences.
+    String(Cow<'a, str>),
+    /// None represents literal empty string "", whatever the tag type.
     None,
 }
 
+/// Declared types of the known Twitch tags.
+/// Unknown tags are always treated as strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagKind {
+    /// Parsed as [`TagValue::Color`].
+    Color,
+    /// Parsed as [`TagValue::Number`].
+    Number,
+    /// Parsed as [`TagValue::Integer`].
+    Integer,
+    /// Parsed as [`TagValue::Timestamp`].
+    Timestamp,
+    /// Parsed as [`TagValue::Boolean`].
+    Boolean,
+    /// Parsed as [`TagValue::String`].
+    String,
+}
+
+impl TagKind {
+    /// Returns the declared type of the tag with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// assert_eq!(TagKind::of("bits"), TagKind::Number);
+    /// assert_eq!(TagKind::of("display-name"), TagKind::String);
+    /// ```
+    pub fn of(key: &str) -> TagKind {
+        match key {
+            "color" => TagKind::Color,
+            "bits"
+            | "ban-duration"
+            | "slow"
+            | "msg-param-cumulative-months"
+            | "msg-param-gift-months"
+            | "msg-param-mass-gift-count"
+            | "msg-param-months"
+            | "msg-param-multimonth-duration"
+            | "msg-param-multimonth-tenure"
+            | "msg-param-sender-count"
+            | "msg-param-streak-months"
+            | "msg-param-threshold"
+            | "msg-param-viewerCount" => TagKind::Number,
+            "followers-only" => TagKind::Integer,
+            "tmi-sent-ts" | "sent-ts" => TagKind::Timestamp,
+            "emote-only"
+            | "first-msg"
+            | "mod"
+            | "msg-param-should-share-streak"
+            | "r9k"
+            | "returning-chatter"
+            | "subs-only"
+            | "subscriber"
+            | "turbo"
+            | "vip" => TagKind::Boolean,
+            _ => TagKind::String,
+        }
+    }
+}
+
 impl<'a> TagValue<'a> {
-    /// Returns a TagValue variant based on the given [`&str`].
+    /// Returns a TagValue variant guessing its type from the given [`&str`].
+    ///
+    /// Note that the guess depends on the value only, so a single digit number "1" or "0"
+    /// is represented as a Boolean value. Prefer [`TagValue::parse`] for tags.
     pub fn new(val: &'a str) -> TagValue<'a> {
         match val {
             "" => TagValue::None,
@@ -89,26 +247,58 @@ impl<'a> TagValue<'a> {
                     TagValue::Number(num)
                 } else if let Ok(tm) = val.parse::<u64>() {
                     TagValue::Timestamp(tm)
-                } else if val.starts_with('#') {
-                    // Try to convert hexadecimal values, used by the 'color' tag, to Number.
-                    if let Ok(num) = u32::from_str_radix(&val[1..], 16) {
-                        TagValue::Color(num)
-                    } else {
-                        TagValue::String(val)
-                    }
+                } else if let Some(color) = Rgb::from_hex(val) {
+                    // Try to convert hexadecimal values, used by the 'color' tag, to Color.
+                    TagValue::Color(color)
                 } else {
-                    TagValue::String(val)
+                    TagValue::String(unescape(val))
                 }
             }
         }
     }
+
+    /// Returns a TagValue variant whose type is declared by the tag name.
+    /// See [`TagKind::of`] for the known tags.
+    ///
+    /// An empty value is always [`TagValue::None`], and a value that does not match
+    /// the declared type is kept as [`TagValue::String`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tmi_parser::*;
+    /// assert_eq!(TagValue::parse("mod", "1"), TagValue::Boolean(true));
+    /// assert_eq!(TagValue::parse("bits", "1"), TagValue::Number(1));
+    /// assert_eq!(TagValue::parse("display-name", "1"), TagValue::String("1".into()));
+    /// ```
+    pub fn parse(key: &str, val: &'a str) -> TagValue<'a> {
+        if val.is_empty() {
+            return TagValue::None;
+        }
+
+        let parsed = match TagKind::of(key) {
+            TagKind::Color => Rgb::from_hex(val).map(TagValue::Color),
+            TagKind::Number => val.parse().ok().map(TagValue::Number),
+            TagKind::Integer => val.parse().ok().map(TagValue::Integer),
+            TagKind::Timestamp => val.parse().ok().map(TagValue::Timestamp),
+            TagKind::Boolean => match val {
+                "0" => Some(TagValue::Boolean(false)),
+                "1" => Some(TagValue::Boolean(true)),
+                _ => None,
+            },
+            TagKind::String => None,
+        };
+
+        parsed.unwrap_or_else(|| TagValue::String(unescape(val)))
+    }
 }
 
 impl<'a> fmt::Display for TagValue<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TagValue::Color(num) => write!(f, "#{:06X}", num),
+            TagValue::Color(color) => write!(f, "{}", color),
             TagValue::Number(num) => write!(f, "{}", num),
+            TagValue::Integer(num) => write!(f, "{}", num),
             TagValue::Timestamp(num) => write!(f, "{}", num),
             TagValue::Boolean(false) => write!(f, "0"),
             TagValue::Boolean(true) => write!(f, "1"),
@@ -117,3 +307,77 @@ impl<'a> fmt::Display for TagValue<'a> {
         }
     }
 }
+
+/// Decodes the IRCv3 escape sequences of a raw tag value.
+/// Returns the borrowed slice if no escape sequence is present.
+///
+/// `\:` becomes `;`, `\s` a space, `\\` a backslash, `\r` and `\n` a carriage return and a line feed.
+/// Any other escaped character stands for itself and a trailing backslash is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// assert_eq!(unescape("ronni\\shas\\ssubscribed!"), "ronni has subscribed!");
+/// ```
+pub fn unescape(val: &str) -> Cow<'_, str> {
+    if !val.contains('\\') {
+        return Cow::Borrowed(val);
+    }
+
+    let mut raw = String::with_capacity(val.len());
+    let mut chars = val.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            raw.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some(':') => raw.push(';'),
+            Some('s') => raw.push(' '),
+            Some('r') => raw.push('\r'),
+            Some('n') => raw.push('\n'),
+            Some(ch) => raw.push(ch),
+            None => break,
+        }
+    }
+
+    Cow::Owned(raw)
+}
+
+/// Encodes a tag value using the IRCv3 escape sequences.
+/// Returns the borrowed slice if no character needs to be escaped.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// assert_eq!(escape("ronni has subscribed!"), "ronni\\shas\\ssubscribed!");
+/// ```
+pub fn escape(val: &str) -> Cow<'_, str> {
+    if !val.contains(&[';', ' ', '\\', '\r', '\n'][..]) {
+        return Cow::Borrowed(val);
+    }
+
+    let mut raw = String::with_capacity(val.len() + 8);
+    escape_to(&mut raw, val).unwrap();
+    Cow::Owned(raw)
+}
+
+/// Helper function for escaping a tag value straight into the given sink.
+fn escape_to<W: fmt::Write>(w: &mut W, val: &str) -> fmt::Result {
+    for ch in val.chars() {
+        match ch {
+            ';' => w.write_str("\\:")?,
+            ' ' => w.write_str("\\s")?,
+            '\\' => w.write_str("\\\\")?,
+            '\r' => w.write_str("\\r")?,
+            '\n' => w.write_str("\\n")?,
+            _ => w.write_char(ch)?,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,438 @@
+//! Typed views over the tags of TMI messages.
+//!
+//! Views borrow the [`Tags`] map of a message and look values up only when accessed.
+
+use crate::{Badges, Emotes, Message, Rgb, TagValue, Tags};
+
+/// Helper function for reading a string tag.
+pub(crate) fn string<'a>(tags: &'a Tags<'a>, key: &str) -> Option<&'a str> {
+    match tags.get(key)? {
+        TagValue::String(val) => Some(val),
+        _ => None,
+    }
+}
+
+/// Helper function for reading a numeric tag.
+pub(crate) fn number(tags: &Tags, key: &str) -> Option<u32> {
+    match tags.get(key)? {
+        TagValue::Number(num) => Some(*num),
+        _ => None,
+    }
+}
+
+/// Helper function for reading a timestamp tag.
+fn timestamp(tags: &Tags, key: &str) -> Option<u64> {
+    match tags.get(key)? {
+        TagValue::Timestamp(num) => Some(*num),
+        _ => None,
+    }
+}
+
+/// Helper function for reading a boolean tag.
+fn boolean(tags: &Tags, key: &str) -> Option<bool> {
+    match tags.get(key)? {
+        TagValue::Boolean(val) => Some(*val),
+        _ => None,
+    }
+}
+
+/// Helper function for reading the `color` tag.
+fn color(tags: &Tags) -> Option<Rgb> {
+    match tags.get("color")? {
+        TagValue::Color(color) => Some(*color),
+        _ => None,
+    }
+}
+
+/// Generates the accessor methods of a typed view.
+macro_rules! accessors {
+    ($($(#[$doc:meta])* $name:ident: $kind:ident($key:literal);)*) => {
+        $(accessors!(@one $(#[$doc])* $name $kind $key);)*
+    };
+    (@one $(#[$doc:meta])* $name:ident str $key:literal) => {
+        $(#[$doc])*
+        pub fn $name(&self) -> Option<&'a str> {
+            string(self.tags, $key)
+        }
+    };
+    (@one $(#[$doc:meta])* $name:ident number $key:literal) => {
+        $(#[$doc])*
+        pub fn $name(&self) -> Option<u32> {
+            number(self.tags, $key)
+        }
+    };
+    (@one $(#[$doc:meta])* $name:ident timestamp $key:literal) => {
+        $(#[$doc])*
+        pub fn $name(&self) -> Option<u64> {
+            timestamp(self.tags, $key)
+        }
+    };
+    (@one $(#[$doc:meta])* $name:ident flag $key:literal) => {
+        $(#[$doc])*
+        pub fn $name(&self) -> bool {
+            boolean(self.tags, $key).unwrap_or(false)
+        }
+    };
+    (@one $(#[$doc:meta])* $name:ident boolean $key:literal) => {
+        $(#[$doc])*
+        pub fn $name(&self) -> Option<bool> {
+            boolean(self.tags, $key)
+        }
+    };
+    (@one $(#[$doc:meta])* $name:ident color $key:literal) => {
+        $(#[$doc])*
+        pub fn $name(&self) -> Option<Rgb> {
+            color(self.tags)
+        }
+    };
+    (@one $(#[$doc:meta])* $name:ident badges $key:literal) => {
+        $(#[$doc])*
+        pub fn $name(&self) -> Option<Badges<'a>> {
+            self.tags.get($key)?.badges()
+        }
+    };
+    (@one $(#[$doc:meta])* $name:ident emotes $key:literal) => {
+        $(#[$doc])*
+        pub fn $name(&self) -> Option<Emotes<'a>> {
+            self.tags.get($key)?.emotes()
+        }
+    };
+}
+
+/// Parent message of a reply, from the `reply-parent-*` tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplyParent<'a> {
+    /// Identifier of the parent message.
+    pub msg_id: &'a str,
+    /// Identifier of the parent message sender.
+    pub user_id: Option<&'a str>,
+    /// Login of the parent message sender.
+    pub user_login: Option<&'a str>,
+    /// Display name of the parent message sender.
+    pub display_name: Option<&'a str>,
+    /// Text of the parent message.
+    pub msg_body: Option<&'a str>,
+}
+
+/// Typed view over the tags of a [`Message::Privmsg`].
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let msg = Message::parse("@bits=100;display-name=ronni PRIVMSG #dallas :cheer100").unwrap();
+/// let tags = msg.privmsg_tags().unwrap();
+/// # assert_eq!(tags.bits(), Some(100));
+/// # assert_eq!(tags.display_name(), Some("ronni"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrivmsgTags<'a> {
+    tags: &'a Tags<'a>,
+}
+
+impl<'a> PrivmsgTags<'a> {
+    /// Returns a typed view over the given tags.
+    pub fn new(tags: &'a Tags<'a>) -> PrivmsgTags<'a> {
+        PrivmsgTags { tags }
+    }
+
+    accessors! {
+        /// Returns the `badge-info` tag.
+        badge_info: badges("badge-info");
+        /// Returns the `badges` tag.
+        badges: badges("badges");
+        /// Returns the amount of cheered bits.
+        bits: number("bits");
+        /// Returns the color of the user name.
+        color: color("color");
+        /// Returns the display name of the user.
+        display_name: str("display-name");
+        /// Returns the `emotes` tag.
+        emotes: emotes("emotes");
+        /// Returns true if this is the first message of the user in the channel.
+        first_msg: flag("first-msg");
+        /// Returns the identifier of the message.
+        id: str("id");
+        /// Returns true if the user is a moderator.
+        is_mod: flag("mod");
+        /// Returns true if the user is a returning chatter.
+        returning_chatter: flag("returning-chatter");
+        /// Returns the identifier of the channel.
+        room_id: str("room-id");
+        /// Returns true if the user is a subscriber.
+        subscriber: flag("subscriber");
+        /// Returns the timestamp of the message, in milliseconds.
+        tmi_sent_ts: timestamp("tmi-sent-ts");
+        /// Returns true if the user has Turbo.
+        turbo: flag("turbo");
+        /// Returns the identifier of the user.
+        user_id: str("user-id");
+        /// Returns the type of the user.
+        user_type: str("user-type");
+        /// Returns true if the user is a VIP.
+        vip: flag("vip");
+    }
+
+    /// Returns the parent message if the message is a reply.
+    pub fn reply_parent(&self) -> Option<ReplyParent<'a>> {
+        Some(ReplyParent {
+            msg_id: string(self.tags, "reply-parent-msg-id")?,
+            user_id: string(self.tags, "reply-parent-user-id"),
+            user_login: string(self.tags, "reply-parent-user-login"),
+            display_name: string(self.tags, "reply-parent-display-name"),
+            msg_body: string(self.tags, "reply-parent-msg-body"),
+        })
+    }
+}
+
+/// Typed view over the tags of a [`Message::Userstate`] or [`Message::GlobalUserstate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UserstateTags<'a> {
+    tags: &'a Tags<'a>,
+}
+
+impl<'a> UserstateTags<'a> {
+    /// Returns a typed view over the given tags.
+    pub fn new(tags: &'a Tags<'a>) -> UserstateTags<'a> {
+        UserstateTags { tags }
+    }
+
+    accessors! {
+        /// Returns the `badge-info` tag.
+        badge_info: badges("badge-info");
+        /// Returns the `badges` tag.
+        badges: badges("badges");
+        /// Returns the color of the user name.
+        color: color("color");
+        /// Returns the display name of the user.
+        display_name: str("display-name");
+        /// Returns the identifier of the message just sent, if any.
+        id: str("id");
+        /// Returns true if the user is a moderator.
+        is_mod: flag("mod");
+        /// Returns true if the user is a subscriber.
+        subscriber: flag("subscriber");
+        /// Returns true if the user has Turbo.
+        turbo: flag("turbo");
+        /// Returns the identifier of the user.
+        /// Only sent with [`Message::GlobalUserstate`].
+        user_id: str("user-id");
+        /// Returns the type of the user.
+        user_type: str("user-type");
+    }
+
+    /// Returns an iterator over the emote sets available to the user.
+    pub fn emote_sets(&self) -> impl Iterator<Item = &'a str> {
+        string(self.tags, "emote-sets")
+            .unwrap_or("")
+            .split(',')
+            .filter(|set| !set.is_empty())
+    }
+}
+
+/// Typed view over the tags of a [`Message::Roomstate`].
+/// Settings are None when not included in a partial update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoomstateTags<'a> {
+    tags: &'a Tags<'a>,
+}
+
+impl<'a> RoomstateTags<'a> {
+    /// Returns a typed view over the given tags.
+    pub fn new(tags: &'a Tags<'a>) -> RoomstateTags<'a> {
+        RoomstateTags { tags }
+    }
+
+    accessors! {
+        /// Returns true if only emotes are allowed.
+        emote_only: boolean("emote-only");
+        /// Returns true if unique chat mode is enabled.
+        r9k: boolean("r9k");
+        /// Returns the identifier of the channel.
+        room_id: str("room-id");
+        /// Returns the slow mode delay, in seconds.
+        slow: number("slow");
+        /// Returns true if only subscribers can chat.
+        subs_only: boolean("subs-only");
+    }
+
+    /// Returns the followers-only minimum follow time in minutes, where -1 means disabled.
+    pub fn followers_only(&self) -> Option<i64> {
+        match self.tags.get("followers-only")? {
+            TagValue::Integer(num) => Some(*num),
+            _ => None,
+        }
+    }
+}
+
+/// Typed view over the tags of a [`Message::Usernotice`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UsernoticeTags<'a> {
+    tags: &'a Tags<'a>,
+}
+
+impl<'a> UsernoticeTags<'a> {
+    /// Returns a typed view over the given tags.
+    pub fn new(tags: &'a Tags<'a>) -> UsernoticeTags<'a> {
+        UsernoticeTags { tags }
+    }
+
+    accessors! {
+        /// Returns the `badge-info` tag.
+        badge_info: badges("badge-info");
+        /// Returns the `badges` tag.
+        badges: badges("badges");
+        /// Returns the color of the user name.
+        color: color("color");
+        /// Returns the display name of the user.
+        display_name: str("display-name");
+        /// Returns the `emotes` tag.
+        emotes: emotes("emotes");
+        /// Returns the identifier of the message.
+        id: str("id");
+        /// Returns true if the user is a moderator.
+        is_mod: flag("mod");
+        /// Returns the login of the user.
+        login: str("login");
+        /// Returns the type of the notice.
+        msg_id: str("msg-id");
+        /// Returns the identifier of the channel.
+        room_id: str("room-id");
+        /// Returns true if the user is a subscriber.
+        subscriber: flag("subscriber");
+        /// Returns the message displayed by the chat for the notice.
+        system_msg: str("system-msg");
+        /// Returns the timestamp of the notice, in milliseconds.
+        tmi_sent_ts: timestamp("tmi-sent-ts");
+        /// Returns true if the user has Turbo.
+        turbo: flag("turbo");
+        /// Returns the identifier of the user.
+        user_id: str("user-id");
+        /// Returns the type of the user.
+        user_type: str("user-type");
+    }
+
+    /// Returns the `msg-param-<name>` tag.
+    pub fn param(&self, name: &str) -> Option<&'a TagValue<'a>> {
+        // The last value wins, as with Tags::get.
+        self.tags
+            .iter()
+            .filter(|(key, _)| key.strip_prefix("msg-param-") == Some(name))
+            .map(|(_, val)| val)
+            .last()
+    }
+}
+
+/// Typed view over the tags of a [`Message::Clearchat`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClearchatTags<'a> {
+    tags: &'a Tags<'a>,
+}
+
+impl<'a> ClearchatTags<'a> {
+    /// Returns a typed view over the given tags.
+    pub fn new(tags: &'a Tags<'a>) -> ClearchatTags<'a> {
+        ClearchatTags { tags }
+    }
+
+    accessors! {
+        /// Returns the timeout duration in seconds, which is missing for permanent bans.
+        ban_duration: number("ban-duration");
+        /// Returns the identifier of the channel.
+        room_id: str("room-id");
+        /// Returns the identifier of the banned user.
+        target_user_id: str("target-user-id");
+        /// Returns the timestamp of the event, in milliseconds.
+        tmi_sent_ts: timestamp("tmi-sent-ts");
+    }
+}
+
+/// Typed view over the tags of a [`Message::Clearmsg`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClearmsgTags<'a> {
+    tags: &'a Tags<'a>,
+}
+
+impl<'a> ClearmsgTags<'a> {
+    /// Returns a typed view over the given tags.
+    pub fn new(tags: &'a Tags<'a>) -> ClearmsgTags<'a> {
+        ClearmsgTags { tags }
+    }
+
+    accessors! {
+        /// Returns the login of the user who sent the deleted message.
+        login: str("login");
+        /// Returns the identifier of the channel.
+        room_id: str("room-id");
+        /// Returns the identifier of the deleted message.
+        target_msg_id: str("target-msg-id");
+        /// Returns the timestamp of the event, in milliseconds.
+        tmi_sent_ts: timestamp("tmi-sent-ts");
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Returns a typed view over the tags of a [`Message::Privmsg`], otherwise None.
+    pub fn privmsg_tags(&self) -> Option<PrivmsgTags<'_>> {
+        match self {
+            Message::Privmsg {
+                tags: Some(tags), ..
+            } => Some(PrivmsgTags::new(tags)),
+            _ => None,
+        }
+    }
+
+    /// Returns a typed view over the tags of a [`Message::Userstate`]
+    /// or a [`Message::GlobalUserstate`], otherwise None.
+    pub fn userstate_tags(&self) -> Option<UserstateTags<'_>> {
+        match self {
+            Message::Userstate {
+                tags: Some(tags), ..
+            }
+            | Message::GlobalUserstate {
+                tags: Some(tags), ..
+            } => Some(UserstateTags::new(tags)),
+            _ => None,
+        }
+    }
+
+    /// Returns a typed view over the tags of a [`Message::Roomstate`], otherwise None.
+    pub fn roomstate_tags(&self) -> Option<RoomstateTags<'_>> {
+        match self {
+            Message::Roomstate {
+                tags: Some(tags), ..
+            } => Some(RoomstateTags::new(tags)),
+            _ => None,
+        }
+    }
+
+    /// Returns a typed view over the tags of a [`Message::Usernotice`], otherwise None.
+    pub fn usernotice_tags(&self) -> Option<UsernoticeTags<'_>> {
+        match self {
+            Message::Usernotice {
+                tags: Some(tags), ..
+            } => Some(UsernoticeTags::new(tags)),
+            _ => None,
+        }
+    }
+
+    /// Returns a typed view over the tags of a [`Message::Clearchat`], otherwise None.
+    pub fn clearchat_tags(&self) -> Option<ClearchatTags<'_>> {
+        match self {
+            Message::Clearchat {
+                tags: Some(tags), ..
+            } => Some(ClearchatTags::new(tags)),
+            _ => None,
+        }
+    }
+
+    /// Returns a typed view over the tags of a [`Message::Clearmsg`], otherwise None.
+    pub fn clearmsg_tags(&self) -> Option<ClearmsgTags<'_>> {
+        match self {
+            Message::Clearmsg {
+                tags: Some(tags), ..
+            } => Some(ClearmsgTags::new(tags)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Events of USERNOTICE messages.
+
+use crate::typed_tags::{number, string};
+use crate::{Message, Tags};
+
+/// Possible subscription plans of the `msg-param-sub-plan` tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubPlan<'a> {
+    /// Represents a Prime Gaming subscription, "Prime".
+    Prime,
+    /// Represents a tier 1 subscription, "1000".
+    Tier1,
+    /// Represents a tier 2 subscription, "2000".
+    Tier2,
+    /// Represents a tier 3 subscription, "3000".
+    Tier3,
+    /// Represents any other plan.
+    Other(&'a str),
+}
+
+impl<'a> SubPlan<'a> {
+    /// Returns a SubPlan variant based on the given [`&str`].
+    pub fn new(plan: &'a str) -> SubPlan<'a> {
+        match plan {
+            "Prime" => SubPlan::Prime,
+            "1000" => SubPlan::Tier1,
+            "2000" => SubPlan::Tier2,
+            "3000" => SubPlan::Tier3,
+            _ => SubPlan::Other(plan),
+        }
+    }
+}
+
+/// Possible colors of the `msg-param-color` tag of announcements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnouncementColor<'a> {
+    /// Represents the channel accent color, "PRIMARY".
+    Primary,
+    /// Represents "BLUE".
+    Blue,
+    /// Represents "GREEN".
+    Green,
+    /// Represents "ORANGE".
+    Orange,
+    /// Represents "PURPLE".
+    Purple,
+    /// Represents any other color.
+    Other(&'a str),
+}
+
+impl<'a> AnnouncementColor<'a> {
+    /// Returns an AnnouncementColor variant based on the given [`&str`].
+    pub fn new(color: &'a str) -> AnnouncementColor<'a> {
+        match color {
+            "PRIMARY" => AnnouncementColor::Primary,
+            "BLUE" => AnnouncementColor::Blue,
+            "GREEN" => AnnouncementColor::Green,
+            "ORANGE" => AnnouncementColor::Orange,
+            "PURPLE" => AnnouncementColor::Purple,
+            _ => AnnouncementColor::Other(color),
+        }
+    }
+}
+
+/// Recipient of a gifted subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recipient<'a> {
+    /// Identifier of the recipient, from `msg-param-recipient-id`.
+    pub id: Option<&'a str>,
+    /// Login of the recipient, from `msg-param-recipient-user-name`.
+    pub login: Option<&'a str>,
+    /// Display name of the recipient, from `msg-param-recipient-display-name`.
+    pub display_name: Option<&'a str>,
+}
+
+/// Possible events of USERNOTICE messages, decoded from the `msg-id` tag
+/// and the related `msg-param-*` tags.
+///
+/// Parameters are None when missing or malformed.
+///
+/// # Examples
+///
+/// ```
+/// # use tmi_parser::*;
+/// let msg = Message::parse("@msg-id=raid;msg-param-login=ronni;msg-param-viewerCount=15 \
+///                           :tmi.twitch.tv USERNOTICE #dallas").unwrap();
+/// # assert!(matches!(msg.usernotice_event(), Some(UsernoticeEvent::Raid { viewers: Some(15), .. })));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsernoticeEvent<'a> {
+    /// Represents a new subscription, `sub`.
+    Sub {
+        months: Option<u32>,
+        plan: Option<SubPlan<'a>>,
+    },
+    /// Represents a renewed subscription, `resub`.
+    Resub {
+        months: Option<u32>,
+        streak: Option<u32>,
+        plan: Option<SubPlan<'a>>,
+    },
+    /// Represents a subscription gifted to a user, `subgift` or `anonsubgift`.
+    Subgift {
+        anonymous: bool,
+        months: Option<u32>,
+        gift_months: Option<u32>,
+        plan: Option<SubPlan<'a>>,
+        recipient: Recipient<'a>,
+    },
+    /// Represents subscriptions gifted to random users, `submysterygift` or `anonsubmysterygift`.
+    Submysterygift {
+        anonymous: bool,
+        count: Option<u32>,
+        sender_count: Option<u32>,
+        plan: Option<SubPlan<'a>>,
+    },
+    /// Represents a gifted subscription continued by the user, `giftpaidupgrade` or `anongiftpaidupgrade`.
+    Giftpaidupgrade {
+        anonymous: bool,
+        sender_login: Option<&'a str>,
+        sender_name: Option<&'a str>,
+    },
+    /// Represents a Prime Gaming subscription continued as a paid one, `primepaidupgrade`.
+    Primepaidupgrade { plan: Option<SubPlan<'a>> },
+    /// Represents an incoming raid, `raid`.
+    Raid {
+        login: Option<&'a str>,
+        display_name: Option<&'a str>,
+        viewers: Option<u32>,
+    },
+    /// Represents a cancelled raid, `unraid`.
+    Unraid,
+    /// Represents a ritual, such as a new chatter greeting, `ritual`.
+    Ritual { name: Option<&'a str> },
+    /// Represents a new bits badge tier, `bitsbadgetier`.
+    Bitsbadgetier { threshold: Option<u32> },
+    /// Represents an announcement, `announcement`.
+    Announcement {
+        color: Option<AnnouncementColor<'a>>,
+    },
+    /// Represents any other `msg-id`.
+    Unknown(&'a str),
+}
+
+impl<'a> UsernoticeEvent<'a> {
+    /// Decodes the event from the tags of a USERNOTICE message.
+    /// Returns None if the `msg-id` tag is missing.
+    pub fn new(tags: &'a Tags<'a>) -> Option<UsernoticeEvent<'a>> {
+        let id = string(tags, "msg-id")?;
+        let plan = string(tags, "msg-param-sub-plan").map(SubPlan::new);
+
+        Some(match id {
+            "sub" => UsernoticeEvent::Sub {
+                months: number(tags, "msg-param-cumulative-months"),
+                plan,
+            },
+            "resub" => UsernoticeEvent::Resub {
+                months: number(tags, "msg-param-cumulative-months"),
+                streak: number(tags, "msg-param-streak-months"),
+                plan,
+            },
+            "subgift" | "anonsubgift" => UsernoticeEvent::Subgift {
+                anonymous: id == "anonsubgift",
+                months: number(tags, "msg-param-months"),
+                gift_months: number(tags, "msg-param-gift-months"),
+                plan,
+                recipient: Recipient {
+                    id: string(tags, "msg-param-recipient-id"),
+                    login: string(tags, "msg-param-recipient-user-name"),
+                    display_name: string(tags, "msg-param-recipient-display-name"),
+                },
+            },
+            "submysterygift" | "anonsubmysterygift" => UsernoticeEvent::Submysterygift {
+                anonymous: id == "anonsubmysterygift",
+                count: number(tags, "msg-param-mass-gift-count"),
+                sender_count: number(tags, "msg-param-sender-count"),
+                plan,
+            },
+            "giftpaidupgrade" | "anongiftpaidupgrade" => UsernoticeEvent::Giftpaidupgrade {
+                anonymous: id == "anongiftpaidupgrade",
+                sender_login: string(tags, "msg-param-sender-login"),
+                sender_name: string(tags, "msg-param-sender-name"),
+            },
+            "primepaidupgrade" => UsernoticeEvent::Primepaidupgrade { plan },
+            "raid" => UsernoticeEvent::Raid {
+                login: string(tags, "msg-param-login"),
+                display_name: string(tags, "msg-param-displayName"),
+                viewers: number(tags, "msg-param-viewerCount"),
+            },
+            "unraid" => UsernoticeEvent::Unraid,
+            "ritual" => UsernoticeEvent::Ritual {
+                name: string(tags, "msg-param-ritual-name"),
+            },
+            "bitsbadgetier" => UsernoticeEvent::Bitsbadgetier {
+                threshold: number(tags, "msg-param-threshold"),
+            },
+            "announcement" => UsernoticeEvent::Announcement {
+                color: string(tags, "msg-param-color").map(AnnouncementColor::new),
+            },
+            _ => UsernoticeEvent::Unknown(id),
+        })
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Returns the event of a [`Message::Usernotice`], otherwise None.
+    pub fn usernotice_event(&self) -> Option<UsernoticeEvent<'_>> {
+        match self {
+            Message::Usernotice {
+                tags: Some(tags), ..
+            } => UsernoticeEvent::new(tags),
+            _ => None,
+        }
+    }
+}
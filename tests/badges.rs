@@ -0,0 +1,54 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn badges() -> Result<()> {
+    let badges = Badges::new("staff/1,broadcaster/1,bits/1000");
+
+    assert_eq!(
+        badges.iter().collect::<Vec<_>>(),
+        vec![("staff", "1"), ("broadcaster", "1"), ("bits", "1000")]
+    );
+
+    assert!(badges.has("broadcaster"));
+    assert!(!badges.has("moderator"));
+    assert_eq!(badges.get("bits"), Some("1000"));
+    assert_eq!(badges.subscriber_months(), None);
+
+    let empty = Badges::new("");
+
+    assert!(empty.is_empty());
+    assert_eq!(empty.iter().next(), None);
+
+    assert_eq!(Badges::new("subscriber/16").subscriber_months(), Some(16));
+    assert_eq!(
+        Badges::new("turbo,").iter().collect::<Vec<_>>(),
+        vec![("turbo", "")]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn message_badges() -> Result<()> {
+    let msg = "@badge-info=subscriber/16;badges=moderator/1,subscriber/12;mod=1 \
+               :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :hi";
+    let msg = Message::parse(msg)?;
+
+    assert!(msg.badges().unwrap().has("moderator"));
+    assert_eq!(msg.badges().unwrap().get("subscriber"), Some("12"));
+    assert_eq!(msg.badge_info().unwrap().subscriber_months(), Some(16));
+
+    let state = Message::parse("@badge-info=;badges= :tmi.twitch.tv USERSTATE #dallas")?;
+
+    assert!(state.badges().unwrap().is_empty());
+    assert!(state.badge_info().unwrap().is_empty());
+
+    assert!(Message::parse("PRIVMSG #dallas :hi")?.badges().is_none());
+
+    let tags = msg.tags().unwrap();
+    assert!(tags.get("badges").and_then(TagValue::badges).is_some());
+    assert!(tags.get("mod").and_then(TagValue::badges).is_none());
+
+    Ok(())
+}
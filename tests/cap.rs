@@ -9,6 +9,7 @@ fn parse_capreq() -> Result<()> {
     assert_eq!(
         Message::parse(cap1)?,
         Message::CapReq {
+            prefix: None,
             req: "twitch.tv/membership"
         }
     );
@@ -16,6 +17,7 @@ fn parse_capreq() -> Result<()> {
     assert_eq!(
         Message::parse(cap2)?,
         Message::CapReq {
+            prefix: None,
             req: "twitch.tv/tags"
         }
     );
@@ -31,6 +33,7 @@ fn parse_capack() -> Result<()> {
     assert_eq!(
         Message::parse(cap1)?,
         Message::CapAck {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             req: "twitch.tv/commands"
         }
     );
@@ -38,9 +41,176 @@ fn parse_capack() -> Result<()> {
     assert_eq!(
         Message::parse(cap2)?,
         Message::CapAck {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             req: "twitch.tv/tags"
         }
     );
 
+    // The last parameter is not always trailing.
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv CAP * ACK twitch.tv/tags")?,
+        Message::CapAck {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            req: "twitch.tv/tags"
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_capnak() -> Result<()> {
+    let cap = ":tmi.twitch.tv CAP * NAK :twitch.tv/foo";
+
+    assert_eq!(
+        Message::parse(cap)?,
+        Message::CapNak {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            req: "twitch.tv/foo"
+        }
+    );
+
+    assert_eq!(Message::parse(cap)?.unparse()?, cap);
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv CAP * NAK twitch.tv/foo")?,
+        Message::CapNak {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            req: "twitch.tv/foo"
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_capls() -> Result<()> {
+    let cap1 = "CAP LS";
+    let cap2 = ":tmi.twitch.tv CAP * LS :twitch.tv/tags twitch.tv/commands twitch.tv/membership";
+
+    assert_eq!(
+        Message::parse(cap1)?,
+        Message::CapLs {
+            prefix: None,
+            version: None,
+            caps: None,
+            more: false
+        }
+    );
+
+    assert_eq!(
+        Message::parse(cap2)?,
+        Message::CapLs {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            version: None,
+            caps: Some("twitch.tv/tags twitch.tv/commands twitch.tv/membership"),
+            more: false
+        }
+    );
+
+    assert_eq!(Message::parse(cap1)?.unparse()?, cap1);
+    assert_eq!(Message::parse(cap2)?.unparse()?, cap2);
+
+    // The version of the negotiation is kept.
+    assert_eq!(
+        Message::parse("CAP LS 302")?,
+        Message::CapLs {
+            prefix: None,
+            version: Some("302"),
+            caps: None,
+            more: false
+        }
+    );
+    assert_eq!(Message::parse("CAP LS 302")?.unparse()?, "CAP LS 302");
+    // Every line of a long listing but the last is marked with `*`.
+    let more = ":tmi.twitch.tv CAP * LS * :twitch.tv/tags";
+
+    assert_eq!(
+        Message::parse(more)?,
+        Message::CapLs {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            version: None,
+            caps: Some("twitch.tv/tags"),
+            more: true
+        }
+    );
+    assert_eq!(Message::parse(more)?.unparse()?, more);
+
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv CAP * LS twitch.tv/tags")?,
+        Message::CapLs {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            version: None,
+            caps: Some("twitch.tv/tags"),
+            more: false
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_caplist() -> Result<()> {
+    let cap1 = "CAP LIST\r\n";
+    let cap2 = ":tmi.twitch.tv CAP * LIST :twitch.tv/tags";
+
+    assert_eq!(
+        Message::parse(cap1)?,
+        Message::CapList {
+            prefix: None,
+            caps: None
+        }
+    );
+
+    assert_eq!(
+        Message::parse(cap2)?,
+        Message::CapList {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            caps: Some("twitch.tv/tags")
+        }
+    );
+
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv CAP * LIST twitch.tv/tags")?,
+        Message::parse(cap2)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_capend() -> Result<()> {
+    assert_eq!(Message::parse("CAP END")?, Message::CapEnd { prefix: None });
+
+    assert_eq!(Message::CapEnd { prefix: None }.unparse()?, "CAP END");
+
+    assert!(Message::parse("CAP END :twitch.tv/tags").is_err());
+    assert!(Message::parse("CAP LS 30a").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn capabilities() -> Result<()> {
+    let cap = ":tmi.twitch.tv CAP * ACK :twitch.tv/tags twitch.tv/commands twitch.tv/membership";
+
+    assert_eq!(
+        Message::parse(cap)?.caps().map(|c| c.collect::<Vec<_>>()),
+        Some(vec![
+            Capability::Tags,
+            Capability::Commands,
+            Capability::Membership
+        ])
+    );
+
+    assert_eq!(
+        Message::parse("CAP REQ :twitch.tv/foo")?
+            .caps()
+            .map(|c| c.collect::<Vec<_>>()),
+        Some(vec![Capability::Other("twitch.tv/foo")])
+    );
+
+    assert!(Message::parse("CAP LS")?.caps().is_none());
+    assert_eq!(Capability::Membership.to_string(), "twitch.tv/membership");
+
     Ok(())
 }
@@ -11,6 +11,7 @@ fn parse_clearchat() -> Result<()> {
         Message::parse(chat1)?,
         Message::Clearchat {
             tags: None,
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "dallas",
             usr: None,
         }
@@ -20,18 +21,20 @@ fn parse_clearchat() -> Result<()> {
         Message::parse(chat2)?,
         Message::Clearchat {
             tags: None,
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "dallas",
             usr: Some("ronni"),
         }
     );
 
     let mut tags = Tags::default();
-    tags.insert("ban-duration", TagValue::String("<ban-duration>"));
+    tags.insert("ban-duration", TagValue::String("<ban-duration>".into()));
 
     assert_eq!(
         Message::parse(chat3)?,
         Message::Clearchat {
             tags: Some(tags),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "<channel>",
             usr: Some("<user>"),
         }
@@ -40,6 +43,55 @@ fn parse_clearchat() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn clearchat_action() -> Result<()> {
+    let clear =
+        Message::parse("@room-id=1337;tmi-sent-ts=1642715756806 :tmi.twitch.tv CLEARCHAT #dallas")?;
+    let ban = Message::parse(
+        "@room-id=1337;target-user-id=1338;tmi-sent-ts=1642715756806 :tmi.twitch.tv CLEARCHAT #dallas :ronni",
+    )?;
+    let timeout = Message::parse(
+        "@ban-duration=350;room-id=1337;target-user-id=1338;tmi-sent-ts=1642719320727 :tmi.twitch.tv CLEARCHAT #dallas :ronni",
+    )?;
+
+    assert_eq!(clear.clearchat_action(), Some(ClearchatAction::ClearAll));
+    assert_eq!(
+        ban.clearchat_action(),
+        Some(ClearchatAction::Ban {
+            login: "ronni",
+            user_id: Some("1338"),
+        })
+    );
+    assert_eq!(
+        timeout.clearchat_action(),
+        Some(ClearchatAction::Timeout {
+            login: "ronni",
+            user_id: Some("1338"),
+            seconds: 350,
+        })
+    );
+    assert_eq!(
+        timeout.clearchat_tags().unwrap().tmi_sent_ts(),
+        Some(1642719320727)
+    );
+
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv CLEARCHAT #dallas :ronni")?.clearchat_action(),
+        Some(ClearchatAction::Ban {
+            login: "ronni",
+            user_id: None,
+        })
+    );
+    assert_eq!(
+        Message::parse("@ban-duration=abc :tmi.twitch.tv CLEARCHAT #dallas :ronni")?
+            .clearchat_action(),
+        None
+    );
+    assert_eq!(Message::parse("JOIN #dallas")?.clearchat_action(), None);
+
+    Ok(())
+}
+
 #[test]
 fn parse_clearmsg() -> Result<()> {
     let msg1 = "  @login=<login>;target-msg-id=<target-msg-id> :tmi.twitch.tv CLEARMSG #<channel> :<message>";
@@ -47,26 +99,28 @@ fn parse_clearmsg() -> Result<()> {
         "   @login=ronni;target-msg-id=abc-123-def :tmi.twitch.tv CLEARMSG #dallas :HeyGuys  \r\n";
 
     let mut tags1 = Tags::default();
-    tags1.insert("login", TagValue::String("<login>"));
-    tags1.insert("target-msg-id", TagValue::String("<target-msg-id>"));
+    tags1.insert("login", TagValue::String("<login>".into()));
+    tags1.insert("target-msg-id", TagValue::String("<target-msg-id>".into()));
 
     assert_eq!(
         Message::parse(msg1)?,
         Message::Clearmsg {
             tags: Some(tags1),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "<channel>",
             msg: "<message>",
         }
     );
 
     let mut tags2 = Tags::default();
-    tags2.insert("login", TagValue::String("ronni"));
-    tags2.insert("target-msg-id", TagValue::String("abc-123-def"));
+    tags2.insert("login", TagValue::String("ronni".into()));
+    tags2.insert("target-msg-id", TagValue::String("abc-123-def".into()));
 
     assert_eq!(
         Message::parse(msg2)?,
         Message::Clearmsg {
             tags: Some(tags2),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "dallas",
             msg: "HeyGuys",
         }
@@ -0,0 +1,73 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn rgb_hex() -> Result<()> {
+    assert_eq!(Rgb::from_hex("#1E90FF"), Some(Rgb::new(0x1E, 0x90, 0xFF)));
+    assert_eq!(Rgb::from_hex("#1e90ff"), Some(Rgb::new(0x1E, 0x90, 0xFF)));
+    assert_eq!(Rgb::from_hex("1E90FF"), None);
+    assert_eq!(Rgb::from_hex("#1E90F"), None);
+    assert_eq!(Rgb::from_hex("#+E90FF"), None);
+    assert_eq!(Rgb::from_hex(""), None);
+
+    assert_eq!(Rgb::new(0x1E, 0x90, 0xFF).to_string(), "#1E90FF");
+    assert_eq!(TagValue::Color(Rgb::new(0, 0, 0)).to_string(), "#000000");
+
+    Ok(())
+}
+
+#[test]
+fn rgb_default() -> Result<()> {
+    // ('r' + 'i') % 15 = 219 % 15 = 9
+    assert_eq!(Rgb::default_for("ronni"), Rgb::from_hex("#D2691E").unwrap());
+    // ('d' + 's') % 15 = 215 % 15 = 5
+    assert_eq!(
+        Rgb::default_for("dallas"),
+        Rgb::from_hex("#9ACD32").unwrap()
+    );
+    assert_eq!(Rgb::default_for(""), Rgb::from_hex("#FF0000").unwrap());
+
+    Ok(())
+}
+
+#[test]
+fn rgb_contrast() -> Result<()> {
+    let black = Rgb::new(0x00, 0x00, 0x00);
+    let white = Rgb::new(0xFF, 0xFF, 0xFF);
+    let blue = Rgb::new(0x00, 0x00, 0xFF);
+
+    assert!((black.contrast(white) - 21.0).abs() < 1e-9);
+    assert!((white.contrast(white) - 1.0).abs() < 1e-9);
+
+    // Readable colors are left untouched.
+    assert_eq!(blue.readable_on(white), blue);
+
+    let dark = Rgb::new(0x18, 0x18, 0x1B);
+    let adjusted = blue.readable_on(dark);
+
+    assert_ne!(adjusted, blue);
+    assert!(adjusted.contrast(dark) >= MIN_CONTRAST);
+
+    let yellow = Rgb::new(0xFF, 0xFF, 0x00);
+    assert!(yellow.readable_on(white).contrast(white) >= MIN_CONTRAST);
+
+    Ok(())
+}
+
+#[test]
+fn message_color() -> Result<()> {
+    let set =
+        Message::parse("@color=#0D4200 :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :hi")?;
+    let unset = Message::parse("@color= :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :hi")?;
+
+    assert_eq!(set.color(), Some(Rgb::new(0x0D, 0x42, 0x00)));
+    assert_eq!(unset.color(), Some(Rgb::default_for("ronni")));
+    assert_eq!(
+        Message::parse("@color=;login=ronni :tmi.twitch.tv USERNOTICE #dallas")?.color(),
+        Some(Rgb::default_for("ronni"))
+    );
+    assert_eq!(Message::parse("PRIVMSG #dallas :hi")?.color(), None);
+    assert_eq!(Message::parse("JOIN #dallas")?.color(), None);
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn emotes() -> Result<()> {
+    let emotes = Emotes::new("25:0-4,12-16/1902:6-10");
+    let list = emotes
+        .iter()
+        .map(|e| {
+            (
+                e.id,
+                e.ranges().map(|r| (r.start, r.end)).collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        list,
+        vec![("25", vec![(0, 5), (12, 17)]), ("1902", vec![(6, 11)])]
+    );
+
+    assert!(Emotes::new("").is_empty());
+    assert_eq!(
+        Emotes::new("25:0-4,x-1,4-2")
+            .iter()
+            .next()
+            .unwrap()
+            .ranges()
+            .map(|r| (r.start, r.end))
+            .collect::<Vec<_>>(),
+        vec![(0, 5)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn emotes_fragments() -> Result<()> {
+    let emotes = Emotes::new("25:0-4,12-16/1902:6-10");
+
+    assert_eq!(
+        emotes.fragments("Kappa Keepo Kappa"),
+        vec![
+            Fragment::Emote {
+                id: "25",
+                text: "Kappa"
+            },
+            Fragment::Text(" "),
+            Fragment::Emote {
+                id: "1902",
+                text: "Keepo"
+            },
+            Fragment::Text(" "),
+            Fragment::Emote {
+                id: "25",
+                text: "Kappa"
+            },
+        ]
+    );
+
+    // Positions count chars: the emoji is a single char but two UTF-16 code units.
+    assert_eq!(
+        Emotes::new("25:2-6").fragments("😀 Kappa é"),
+        vec![
+            Fragment::Text("😀 "),
+            Fragment::Emote {
+                id: "25",
+                text: "Kappa"
+            },
+            Fragment::Text(" é"),
+        ]
+    );
+
+    // Out of bounds and overlapping ranges are ignored.
+    assert_eq!(
+        Emotes::new("25:0-4,2-3/1902:6-99").fragments("Kappa hi"),
+        vec![
+            Fragment::Emote {
+                id: "25",
+                text: "Kappa"
+            },
+            Fragment::Text(" hi"),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn message_fragments() -> Result<()> {
+    let msg = Message::parse(
+        "@badge-info=;emotes=25:0-4,12-16/1902:6-10 :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #ronni :Kappa Keepo Kappa",
+    )?;
+
+    assert_eq!(msg.emotes().map(|e| e.iter().count()), Some(2));
+    assert_eq!(msg.fragments().map(|f| f.len()), Some(5));
+
+    assert_eq!(
+        Message::parse("PRIVMSG #dallas :hi")?.fragments(),
+        Some(vec![Fragment::Text("hi")])
+    );
+
+    // Emote positions of /me messages count from the text after the ACTION wrapper.
+    assert_eq!(
+        Message::parse("@emotes=25:0-4 PRIVMSG #dallas :\x01ACTION Kappa hi\x01")?.fragments(),
+        Some(vec![
+            Fragment::Emote {
+                id: "25",
+                text: "Kappa"
+            },
+            Fragment::Text(" hi"),
+        ])
+    );
+
+    assert_eq!(Message::parse("JOIN #dallas")?.fragments(), None);
+
+    Ok(())
+}
@@ -0,0 +1,426 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn encode_client() -> Result<()> {
+    let msg =
+        Message::parse("@client-nonce=abc :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :hi")?;
+
+    assert_eq!(
+        msg.encode(Role::Client)?,
+        "@client-nonce=abc PRIVMSG #dallas :hi"
+    );
+    assert_eq!(
+        Message::CapReq {
+            prefix: None,
+            req: "twitch.tv/tags"
+        }
+        .encode(Role::Client)?,
+        "CAP REQ :twitch.tv/tags"
+    );
+    assert_eq!(
+        Message::parse("PING :tmi.twitch.tv")?
+            .reply()
+            .unwrap()
+            .encode(Role::Client)?,
+        "PONG :tmi.twitch.tv"
+    );
+
+    // Only the tags a client can send are kept.
+    let msg = Message::parse(
+        "@badges=moderator/1;client-nonce=abc;+example=1;reply-parent-msg-id=b34ccfc7;user-type=mod \
+         :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :hi",
+    )?;
+
+    assert_eq!(
+        msg.encode(Role::Client)?,
+        "@client-nonce=abc;+example=1;reply-parent-msg-id=b34ccfc7 PRIVMSG #dallas :hi"
+    );
+    assert_eq!(
+        Message::parse("@badges=moderator/1;user-type=mod PRIVMSG #dallas :hi")?
+            .encode(Role::Client)?,
+        "PRIVMSG #dallas :hi"
+    );
+    assert_eq!(
+        Message::parse("@badges=moderator/1 PRIVMSG #dallas :hi")?.encode(Role::Server),
+        Err(EncodeError::MissingPrefix {
+            command: "PRIVMSG".into()
+        })
+    );
+
+    let state = Message::GlobalUserstate {
+        tags: None,
+        prefix: None,
+    };
+
+    assert_eq!(
+        state.encode(Role::Client),
+        Err(EncodeError::ServerOnly {
+            command: "GLOBALUSERSTATE".into()
+        })
+    );
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv CAP * ACK :twitch.tv/tags")?.encode(Role::Client),
+        Err(EncodeError::ServerOnly {
+            command: "CAP".into()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn encode_server() -> Result<()> {
+    let state = Message::Roomstate {
+        tags: None,
+        prefix: None,
+        chan: "dallas",
+    };
+
+    assert_eq!(
+        state.encode(Role::Server)?,
+        ":tmi.twitch.tv ROOMSTATE #dallas"
+    );
+    assert_eq!(
+        Message::parse(":ronni.tmi.twitch.tv 353 ronni = #dallas :ronni")?.encode(Role::Server)?,
+        ":ronni.tmi.twitch.tv 353 ronni = #dallas :ronni"
+    );
+    assert_eq!(
+        Message::Ping {
+            prefix: None,
+            token: Some("tmi.twitch.tv")
+        }
+        .encode(Role::Server)?,
+        "PING :tmi.twitch.tv"
+    );
+
+    let msg = ":ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :hi";
+    assert_eq!(Message::parse(msg)?.encode(Role::Server)?, msg);
+
+    assert_eq!(
+        Message::parse("PRIVMSG #dallas :hi")?.encode(Role::Server),
+        Err(EncodeError::MissingPrefix {
+            command: "PRIVMSG".into()
+        })
+    );
+    assert_eq!(
+        Message::parse("PASS oauth:abcdef")?.encode(Role::Server),
+        Err(EncodeError::ClientOnly {
+            command: "PASS".into()
+        })
+    );
+    assert_eq!(
+        Message::parse("CAP LS")?.encode(Role::Server),
+        Err(EncodeError::ClientOnly {
+            command: "CAP".into()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn encode_error() -> Result<()> {
+    let err = Message::Nick {
+        prefix: None,
+        nick: "ronni",
+    }
+    .encode(Role::Server)
+    .unwrap_err();
+
+    assert_eq!(err.command(), Some("NICK"));
+    assert_eq!(
+        err.to_string(),
+        "The NICK command can only be sent by a client."
+    );
+
+    let err = Message::parse(":ronni.tmi.twitch.tv 353 ronni = #dallas :ronni")?
+        .encode(Role::Client)
+        .unwrap_err();
+
+    assert_eq!(err.command(), Some("353"));
+    assert_eq!(
+        err.to_string(),
+        "The 353 command can only be sent by the server."
+    );
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv 001 ronni :Welcome, GLHF!")?
+            .encode(Role::Client)
+            .unwrap_err()
+            .command(),
+        Some("001")
+    );
+
+    let err: std::io::Error = err.into();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
+
+#[test]
+fn encode_limits() -> Result<()> {
+    let privmsg = |msg| Message::Privmsg {
+        tags: None,
+        prefix: None,
+        chan: "dallas",
+        msg,
+    };
+    let privmsg_to = |chan| Message::Privmsg {
+        tags: None,
+        prefix: None,
+        chan,
+        msg: "hi",
+    };
+
+    assert_eq!(
+        privmsg("hi\r\nPRIVMSG #ronni :injected").encode(Role::Client),
+        Err(EncodeError::InvalidCharacter {
+            command: "PRIVMSG".into()
+        })
+    );
+    assert_eq!(
+        privmsg("hi\0").encode(Role::Client),
+        Err(EncodeError::InvalidCharacter {
+            command: "PRIVMSG".into()
+        })
+    );
+    assert_eq!(
+        Message::Join {
+            prefix: None,
+            chan: "dallas\nPART #ronni"
+        }
+        .encode(Role::Client),
+        Err(EncodeError::InvalidCharacter {
+            command: "JOIN".into()
+        })
+    );
+
+    // Parameters written as single words cannot be empty or contain spaces.
+    assert_eq!(
+        privmsg_to("dallas :pwned").encode(Role::Client),
+        Err(EncodeError::InvalidParam {
+            command: "PRIVMSG".into()
+        })
+    );
+    assert_eq!(
+        Message::Join {
+            prefix: None,
+            chan: "a PART #b"
+        }
+        .encode(Role::Client),
+        Err(EncodeError::InvalidParam {
+            command: "JOIN".into()
+        })
+    );
+    assert_eq!(
+        privmsg_to("").encode(Role::Client),
+        Err(EncodeError::InvalidParam {
+            command: "PRIVMSG".into()
+        })
+    );
+    assert_eq!(
+        Message::Pass {
+            prefix: None,
+            pass: ""
+        }
+        .encode(Role::Client),
+        Err(EncodeError::InvalidParam {
+            command: "PASS".into()
+        })
+    );
+    assert_eq!(
+        Message::Nick {
+            prefix: None,
+            nick: ":ronni"
+        }
+        .encode(Role::Client),
+        Err(EncodeError::InvalidParam {
+            command: "NICK".into()
+        })
+    );
+    assert_eq!(
+        Message::Whisper {
+            tags: None,
+            from: Prefix::Server("tmi.twitch.tv"),
+            to: "ronni dallas",
+            msg: "hi",
+        }
+        .encode(Role::Server),
+        Err(EncodeError::InvalidParam {
+            command: "WHISPER".into()
+        })
+    );
+    assert_eq!(
+        Message::HosttargetStart {
+            prefix: None,
+            host: "dallas",
+            chan: "ronni 10",
+            view: None,
+        }
+        .encode(Role::Server),
+        Err(EncodeError::InvalidParam {
+            command: "HOSTTARGET".into()
+        })
+    );
+
+    // Tokens are trailing parameters, so any reply to a PING can be encoded.
+    for token in ["tmi twitch", ":tmi.twitch.tv", ""].iter() {
+        let ping = Message::Ping {
+            prefix: None,
+            token: Some(token),
+        };
+
+        assert_eq!(
+            ping.reply().unwrap().encode(Role::Client)?,
+            format!("PONG :{}", token)
+        );
+    }
+    assert_eq!(
+        Message::Pong {
+            prefix: None,
+            token: Some("tmi\r\nJOIN #ronni")
+        }
+        .encode(Role::Client),
+        Err(EncodeError::InvalidCharacter {
+            command: "PONG".into()
+        })
+    );
+    assert!(Message::Pass {
+        prefix: None,
+        pass: "oauth:abcdef"
+    }
+    .encode(Role::Client)
+    .is_ok());
+
+    // Tag keys cannot be empty or contain spaces, semicolons or equal signs.
+    for key in ["+a b", "+a;b", "+a=b"].iter() {
+        let mut tags = Tags::default();
+        tags.insert(key, TagValue::String("x".into()));
+
+        assert_eq!(
+            Message::Privmsg {
+                tags: Some(tags),
+                prefix: None,
+                chan: "dallas",
+                msg: "hi",
+            }
+            .encode(Role::Client),
+            Err(EncodeError::InvalidTag {
+                command: "PRIVMSG".into()
+            }),
+            "{:?}",
+            key
+        );
+    }
+
+    let mut tags = Tags::default();
+    tags.insert("", TagValue::String("x".into()));
+
+    assert_eq!(
+        Message::Roomstate {
+            tags: Some(tags),
+            prefix: None,
+            chan: "dallas",
+        }
+        .encode(Role::Server),
+        Err(EncodeError::InvalidTag {
+            command: "ROOMSTATE".into()
+        })
+    );
+
+    // Escaped tag values are allowed to contain line breaks.
+    let mut tags = Tags::default();
+    tags.insert("client-nonce", TagValue::String("a\r\nb".into()));
+
+    assert_eq!(
+        Message::Privmsg {
+            tags: Some(tags),
+            prefix: None,
+            chan: "dallas",
+            msg: "hi",
+        }
+        .encode(Role::Client)?,
+        "@client-nonce=a\\r\\nb PRIVMSG #dallas :hi"
+    );
+
+    let text = "a".repeat(490);
+    assert!(privmsg(&text).encode(Role::Client).is_ok());
+
+    let text = "a".repeat(495);
+    assert_eq!(
+        privmsg(&text).encode(Role::Client),
+        Err(EncodeError::LineTooLong {
+            command: "PRIVMSG".into(),
+            len: 514
+        })
+    );
+
+    let text = "a".repeat(501);
+    assert_eq!(
+        privmsg(&text).encode(Role::Client),
+        Err(EncodeError::MessageTooLong {
+            command: "PRIVMSG".into(),
+            len: 501
+        })
+    );
+
+    // 200 chars, but 800 bytes.
+    let text = "😀".repeat(200);
+    assert_eq!(
+        privmsg(&text).encode(Role::Client),
+        Err(EncodeError::LineTooLong {
+            command: "PRIVMSG".into(),
+            len: 819
+        })
+    );
+
+    let nonce = "a".repeat(MAX_TAGS_LEN);
+    let mut tags = Tags::default();
+    tags.insert("client-nonce", TagValue::String(nonce.as_str().into()));
+
+    assert!(matches!(
+        Message::Privmsg {
+            tags: Some(tags),
+            prefix: None,
+            chan: "dallas",
+            msg: "hi",
+        }
+        .encode(Role::Client),
+        Err(EncodeError::TagsTooLong { .. })
+    ));
+
+    // Clients have a smaller budget for tags than the server.
+    let tagged = |len: usize| {
+        let mut tags = Tags::default();
+        // The leading `@`, the key, the `=` and the trailing space take 15 bytes.
+        tags.insert(
+            "client-nonce",
+            TagValue::String("a".repeat(len - 15).into()),
+        );
+
+        Message::Privmsg {
+            tags: Some(tags),
+            prefix: Some(Prefix::User {
+                nick: "ronni",
+                user: None,
+                host: None,
+            }),
+            chan: "dallas",
+            msg: "hi",
+        }
+    };
+
+    assert!(tagged(MAX_CLIENT_TAGS_LEN).encode(Role::Client).is_ok());
+    assert_eq!(
+        tagged(MAX_CLIENT_TAGS_LEN + 1).encode(Role::Client),
+        Err(EncodeError::TagsTooLong {
+            command: "PRIVMSG".into(),
+            len: MAX_CLIENT_TAGS_LEN + 1
+        })
+    );
+    assert!(tagged(MAX_CLIENT_TAGS_LEN + 1).encode(Role::Server).is_ok());
+    assert!(tagged(MAX_TAGS_LEN).encode(Role::Server).is_ok());
+    assert!(tagged(MAX_TAGS_LEN + 1).encode(Role::Server).is_err());
+
+    Ok(())
+}
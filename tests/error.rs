@@ -0,0 +1,82 @@
+use tmi_parser::*;
+
+#[test]
+fn parse_error() {
+    assert_eq!(Message::parse("  \r\n"), Err(ParseError::Empty));
+
+    assert_eq!(
+        Message::parse("@mod=1"),
+        Err(ParseError::MalformedTags { offset: 0 })
+    );
+
+    // Offsets point at the empty key and at the extra space.
+    assert_eq!(
+        Message::parse("@a=b;=c :tmi.twitch.tv ROOMSTATE #dallas"),
+        Err(ParseError::MalformedTags { offset: 5 })
+    );
+    assert_eq!(
+        Message::parse(" @a=b;;c=d ROOMSTATE #dallas"),
+        Err(ParseError::MalformedTags { offset: 6 })
+    );
+    assert_eq!(
+        Message::parse("@a=b  PRIVMSG #dallas :hi"),
+        Err(ParseError::MalformedTags { offset: 5 })
+    );
+
+    assert_eq!(
+        Message::parse(":!ronni@ronni.tmi.twitch.tv JOIN #dallas"),
+        Err(ParseError::MalformedPrefix { offset: 0 })
+    );
+
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv FOO #dallas"),
+        Err(ParseError::UnknownCommand {
+            offset: 15,
+            command: String::from("FOO"),
+        })
+    );
+
+    assert_eq!(
+        Message::parse("PRIVMSG dallas :hi"),
+        Err(ParseError::MissingChannel {
+            offset: 8,
+            command: "PRIVMSG",
+        })
+    );
+
+    assert_eq!(
+        Message::parse(" PRIVMSG #dallas"),
+        Err(ParseError::MissingTrailing {
+            offset: 16,
+            command: "PRIVMSG",
+        })
+    );
+
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv CAP * NOPE :twitch.tv/tags"),
+        Err(ParseError::MalformedParams {
+            offset: 19,
+            command: "CAP",
+        })
+    );
+
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv HOSTTARGET #dallas :ronni many"),
+        Err(ParseError::InvalidViewers {
+            offset: 41,
+            command: "HOSTTARGET",
+        })
+    );
+}
+
+#[test]
+fn parse_error_info() {
+    let err = Message::parse(":tmi.twitch.tv FOO #dallas").unwrap_err();
+
+    assert_eq!(err.offset(), 15);
+    assert_eq!(err.command(), Some("FOO"));
+    assert_eq!(err.to_string(), "Unknown command FOO at byte 15.");
+
+    let err: std::io::Error = ParseError::Empty.into();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
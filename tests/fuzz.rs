@@ -0,0 +1,40 @@
+use std::fs;
+use std::io::Result;
+use std::path::Path;
+use tmi_parser::*;
+
+/// Replays the regression corpus found by fuzzing `Message::parse`.
+/// Every input must either parse or return an error, never panic.
+#[test]
+fn parse_corpus() -> Result<()> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+
+    for entry in fs::read_dir(dir)? {
+        let data = fs::read(entry?.path())?;
+
+        if let Ok(msg) = std::str::from_utf8(&data) {
+            if let Ok(msg) = Message::parse(msg) {
+                msg.unparse()?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parse_tag_without_value() -> Result<()> {
+    let mut tags = Tags::default();
+    tags.insert("mod", TagValue::None);
+
+    assert_eq!(
+        Message::parse("@mod :tmi.twitch.tv ROOMSTATE #dallas")?,
+        Message::Roomstate {
+            tags: Some(tags),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            chan: "dallas",
+        }
+    );
+
+    Ok(())
+}
@@ -4,11 +4,12 @@ use tmi_parser::*;
 #[test]
 fn parse_hosttarget_start() -> Result<()> {
     let host1 = ":tmi.twitch.tv HOSTTARGET #hosting_channel :<channel>";
-    let host2 = "tmi.twitch.tv HOSTTARGET #hosting_channel :<channel> 123456";
+    let host2 = ":tmi.twitch.tv HOSTTARGET #hosting_channel :<channel> 123456";
 
     assert_eq!(
         Message::parse(host1)?,
         Message::HosttargetStart {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             host: "hosting_channel",
             chan: "<channel>",
             view: None,
@@ -18,6 +19,7 @@ fn parse_hosttarget_start() -> Result<()> {
     assert_eq!(
         Message::parse(host2)?,
         Message::HosttargetStart {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             host: "hosting_channel",
             chan: "<channel>",
             view: Some(123456u32),
@@ -35,6 +37,7 @@ fn parse_hosttarget_end() -> Result<()> {
     assert_eq!(
         Message::parse(host1)?,
         Message::HosttargetEnd {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             host: "hosting_channel",
             view: None,
         }
@@ -43,6 +46,7 @@ fn parse_hosttarget_end() -> Result<()> {
     assert_eq!(
         Message::parse(host2)?,
         Message::HosttargetEnd {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             host: "hosting_channel",
             view: Some(123456u32),
         }
@@ -6,9 +6,39 @@ fn parse_join() -> Result<()> {
     let join1 = "JOIN #<channel>";
     let join2 = ":ronni!ronni@ronni.tmi.twitch.tv JOIN #dallas";
 
-    assert_eq!(Message::parse(join1)?, Message::Join { chan: "<channel>" });
+    assert_eq!(
+        Message::parse(join1)?,
+        Message::Join {
+            prefix: None,
+            chan: "<channel>"
+        }
+    );
 
-    assert_eq!(Message::parse(join2)?, Message::Join { chan: "dallas" });
+    assert_eq!(
+        Message::parse(join2)?,
+        Message::Join {
+            prefix: Some(Prefix::User {
+                nick: "ronni",
+                user: Some("ronni"),
+                host: Some("ronni.tmi.twitch.tv"),
+            }),
+            chan: "dallas"
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn join_membership() -> Result<()> {
+    let join1 = ":ronni!ronni@ronni.tmi.twitch.tv JOIN #dallas";
+    let join2 = "JOIN #dallas";
+
+    assert_eq!(Message::parse(join1)?.login(), Some("ronni"));
+    assert_eq!(Message::parse(join2)?.login(), None);
+
+    assert_eq!(Message::parse(join1)?.unparse()?, join1);
+    assert_eq!(Message::parse(join2)?.unparse()?, join2);
 
     Ok(())
 }
@@ -6,9 +6,21 @@ fn parse_ping() -> Result<()> {
     let ping1 = "PING :tmi.twitch.tv";
     let ping2 = "      PING :tmi.twitch.tv \r\n";
 
-    assert_eq!(Message::parse(ping1)?, Message::Ping);
+    assert_eq!(
+        Message::parse(ping1)?,
+        Message::Ping {
+            prefix: None,
+            token: Some("tmi.twitch.tv")
+        }
+    );
 
-    assert_eq!(Message::parse(ping2)?, Message::Ping);
+    assert_eq!(
+        Message::parse(ping2)?,
+        Message::Ping {
+            prefix: None,
+            token: Some("tmi.twitch.tv")
+        }
+    );
 
     Ok(())
 }
@@ -18,9 +30,61 @@ fn parse_pong() -> Result<()> {
     let pong1 = " PONG :tmi.twitch.tv";
     let pong2 = "PONG :tmi.twitch.tv \r\n";
 
-    assert_eq!(Message::parse(pong1)?, Message::Pong);
+    assert_eq!(
+        Message::parse(pong1)?,
+        Message::Pong {
+            prefix: None,
+            token: Some("tmi.twitch.tv")
+        }
+    );
 
-    assert_eq!(Message::parse(pong2)?, Message::Pong);
+    assert_eq!(
+        Message::parse(pong2)?,
+        Message::Pong {
+            prefix: None,
+            token: Some("tmi.twitch.tv")
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ping_token() -> Result<()> {
+    let ping1 = "PING :custom-token-1337";
+    let ping2 = "PING";
+
+    assert_eq!(
+        Message::parse(ping1)?,
+        Message::Ping {
+            prefix: None,
+            token: Some("custom-token-1337")
+        }
+    );
+
+    assert_eq!(
+        Message::parse(ping2)?,
+        Message::Ping {
+            prefix: None,
+            token: None
+        }
+    );
+
+    assert_eq!(
+        Message::parse(ping1)?.reply(),
+        Some(Message::Pong {
+            prefix: None,
+            token: Some("custom-token-1337")
+        })
+    );
+
+    assert_eq!(
+        Message::parse(ping1)?.reply().unwrap().unparse()?,
+        "PONG :custom-token-1337"
+    );
+
+    assert_eq!(Message::parse(ping2)?.reply().unwrap().unparse()?, "PONG");
+    assert_eq!(Message::parse("PONG :tmi.twitch.tv")?.reply(), None);
 
     Ok(())
 }
@@ -30,9 +94,9 @@ fn parse_reconnect() -> Result<()> {
     let rec1 = "RECONNECT";
     let rec2 = "  RECONNECT \r\n";
 
-    assert_eq!(Message::parse(rec1)?, Message::Reconnect);
+    assert_eq!(Message::parse(rec1)?, Message::Reconnect { prefix: None });
 
-    assert_eq!(Message::parse(rec2)?, Message::Reconnect);
+    assert_eq!(Message::parse(rec2)?, Message::Reconnect { prefix: None });
 
     Ok(())
 }
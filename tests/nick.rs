@@ -9,6 +9,7 @@ fn parse_nick() -> Result<()> {
     assert_eq!(
         Message::parse(nick1)?,
         Message::Nick {
+            prefix: None,
             nick: "justinfan232456"
         }
     );
@@ -16,6 +17,7 @@ fn parse_nick() -> Result<()> {
     assert_eq!(
         Message::parse(nick2)?,
         Message::Nick {
+            prefix: None,
             nick: "justinfan2adasdasd"
         }
     );
@@ -3,30 +3,32 @@ use tmi_parser::*;
 
 #[test]
 fn parse_notice() -> Result<()> {
-    let note1 = "@msg-id=<msg id> :tmi.twitch.tv NOTICE #<channel> :<message>";
+    let note1 = "@msg-id=<msg-id> :tmi.twitch.tv NOTICE #<channel> :<message>";
     let note2 =
         "@msg-id=slow_off :tmi.twitch.tv NOTICE #dallas :This room is no longer in slow mode.\r\n";
 
     let mut tags1 = Tags::default();
-    tags1.insert("msg-id", TagValue::String("<msg id>"));
+    tags1.insert("msg-id", TagValue::String("<msg-id>".into()));
 
     assert_eq!(
         Message::parse(note1)?,
         Message::Notice {
             tags: Some(tags1),
-            chan: "<channel>",
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            chan: Some("<channel>"),
             msg: "<message>",
         }
     );
 
     let mut tags2 = Tags::default();
-    tags2.insert("msg-id", TagValue::String("slow_off"));
+    tags2.insert("msg-id", TagValue::String("slow_off".into()));
 
     assert_eq!(
         Message::parse(note2)?,
         Message::Notice {
             tags: Some(tags2),
-            chan: "dallas",
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            chan: Some("dallas"),
             msg: "This room is no longer in slow mode.",
         }
     );
@@ -34,6 +36,51 @@ fn parse_notice() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn parse_global_notice() -> Result<()> {
+    let note = ":tmi.twitch.tv NOTICE * :Login authentication failed";
+    let msg = Message::parse(note)?;
+
+    assert_eq!(
+        msg,
+        Message::Notice {
+            tags: None,
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            chan: None,
+            msg: "Login authentication failed",
+        }
+    );
+    assert_eq!(msg.unparse()?, note);
+    assert_eq!(msg.notice_kind(), Some(NoticeKind::LoginFailed));
+
+    Ok(())
+}
+
+#[test]
+fn notice_kind() -> Result<()> {
+    let msg = Message::parse(
+        "@msg-id=msg_banned :tmi.twitch.tv NOTICE #dallas :You are permanently banned.",
+    )?;
+    assert_eq!(msg.notice_kind(), Some(NoticeKind::MsgBanned));
+
+    let msg = Message::parse("@msg-id=followers_on_zero :tmi.twitch.tv NOTICE #dallas :This room is now in followers-only mode.")?;
+    assert_eq!(msg.notice_kind(), Some(NoticeKind::FollowersOn));
+
+    let msg = Message::parse("@msg-id=bad_new_thing :tmi.twitch.tv NOTICE #dallas :?")?;
+    assert_eq!(
+        msg.notice_kind(),
+        Some(NoticeKind::Unknown("bad_new_thing"))
+    );
+
+    let msg = Message::parse(":tmi.twitch.tv NOTICE #dallas :hello")?;
+    assert_eq!(msg.notice_kind(), None);
+
+    let msg = Message::parse("PRIVMSG #dallas :hello")?;
+    assert_eq!(msg.notice_kind(), None);
+
+    Ok(())
+}
+
 #[test]
 fn parse_usernotice() -> Result<()> {
     let note1 = "@badge-info=<badge-info>;badges=<badges>;color=<color>;display-name=<display-name>;\
@@ -48,72 +95,97 @@ fn parse_usernotice() -> Result<()> {
                 turbo=1;user-id=1337;user-type=staff :tmi.twitch.tv USERNOTICE #dallas :Great stream -- keep it up!\r\n";
 
     let mut tags1 = Tags::default();
-    tags1.insert("badge-info", TagValue::String("<badge-info>"));
-    tags1.insert("badges", TagValue::String("<badges>"));
-    tags1.insert("color", TagValue::String("<color>"));
-    tags1.insert("display-name", TagValue::String("<display-name>"));
-    tags1.insert("emotes", TagValue::String("<emotes>"));
-    tags1.insert("id", TagValue::String("<id-of-msg>"));
-    tags1.insert("login", TagValue::String("<user>"));
-    tags1.insert("mod", TagValue::String("<mod>"));
-    tags1.insert("msg-id", TagValue::String("<msg-id>"));
-    tags1.insert("room-id", TagValue::String("<room-id>"));
-    tags1.insert("subscriber", TagValue::String("<subscriber>"));
-    tags1.insert("system-msg", TagValue::String("<system-msg>"));
-    tags1.insert("tmi-sent-ts", TagValue::String("<timestamp>"));
-    tags1.insert("turbo", TagValue::String("<turbo>"));
-    tags1.insert("user-id", TagValue::String("<user-id>"));
-    tags1.insert("user-type", TagValue::String("<user-type>"));
+    tags1.insert("badge-info", TagValue::String("<badge-info>".into()));
+    tags1.insert("badges", TagValue::String("<badges>".into()));
+    tags1.insert("color", TagValue::String("<color>".into()));
+    tags1.insert("display-name", TagValue::String("<display-name>".into()));
+    tags1.insert("emotes", TagValue::String("<emotes>".into()));
+    tags1.insert("id", TagValue::String("<id-of-msg>".into()));
+    tags1.insert("login", TagValue::String("<user>".into()));
+    tags1.insert("mod", TagValue::String("<mod>".into()));
+    tags1.insert("msg-id", TagValue::String("<msg-id>".into()));
+    tags1.insert("room-id", TagValue::String("<room-id>".into()));
+    tags1.insert("subscriber", TagValue::String("<subscriber>".into()));
+    tags1.insert("system-msg", TagValue::String("<system-msg>".into()));
+    tags1.insert("tmi-sent-ts", TagValue::String("<timestamp>".into()));
+    tags1.insert("turbo", TagValue::String("<turbo>".into()));
+    tags1.insert("user-id", TagValue::String("<user-id>".into()));
+    tags1.insert("user-type", TagValue::String("<user-type>".into()));
 
     assert_eq!(
         Message::parse(note1)?,
         Message::Usernotice {
             tags: Some(tags1),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "<channel>",
-            msg: "<message>",
+            msg: Some("<message>"),
         }
     );
 
     let mut tags2 = Tags::default();
     tags2.insert("badge-info", TagValue::None);
-    tags2.insert("badges", TagValue::String("staff/1,broadcaster/1,turbo/1"));
     tags2.insert(
-        "color",
-        TagValue::Color(u32::from_str_radix("008000", 16).unwrap()),
+        "badges",
+        TagValue::String("staff/1,broadcaster/1,turbo/1".into()),
     );
-    tags2.insert("display-name", TagValue::String("ronni"));
+    tags2.insert("color", TagValue::Color(Rgb::from_hex("#008000").unwrap()));
+    tags2.insert("display-name", TagValue::String("ronni".into()));
     tags2.insert("emotes", TagValue::None);
     tags2.insert(
         "id",
-        TagValue::String("db25007f-7a18-43eb-9379-80131e44d633"),
+        TagValue::String("db25007f-7a18-43eb-9379-80131e44d633".into()),
     );
-    tags2.insert("login", TagValue::String("ronni"));
+    tags2.insert("login", TagValue::String("ronni".into()));
     tags2.insert("mod", TagValue::Boolean(false));
-    tags2.insert("msg-id", TagValue::String("resub"));
+    tags2.insert("msg-id", TagValue::String("resub".into()));
     tags2.insert("msg-param-cumulative-months", TagValue::Number(6));
     tags2.insert("msg-param-streak-months", TagValue::Number(2));
     tags2.insert("msg-param-should-share-streak", TagValue::Boolean(true));
-    tags2.insert("msg-param-sub-plan", TagValue::String("Prime"));
-    tags2.insert("msg-param-sub-plan-name", TagValue::String("Prime"));
-    tags2.insert("room-id", TagValue::Number(1337u32));
+    tags2.insert("msg-param-sub-plan", TagValue::String("Prime".into()));
+    tags2.insert("msg-param-sub-plan-name", TagValue::String("Prime".into()));
+    tags2.insert("room-id", TagValue::String("1337".into()));
     tags2.insert("subscriber", TagValue::Boolean(true));
     tags2.insert(
         "system-msg",
-        TagValue::String("ronni\\shas\\ssubscribed\\sfor\\s6\\smonths!"),
+        TagValue::String("ronni has subscribed for 6 months!".into()),
     );
     tags2.insert("tmi-sent-ts", TagValue::Timestamp(1507246572675u64));
     tags2.insert("turbo", TagValue::Boolean(true));
-    tags2.insert("user-id", TagValue::Number(1337u32));
-    tags2.insert("user-type", TagValue::String("staff"));
+    tags2.insert("user-id", TagValue::String("1337".into()));
+    tags2.insert("user-type", TagValue::String("staff".into()));
 
     assert_eq!(
         Message::parse(note2)?,
         Message::Usernotice {
             tags: Some(tags2),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            chan: "dallas",
+            msg: Some("Great stream -- keep it up!"),
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_usernotice_without_message() -> Result<()> {
+    let note = "@msg-id=sub;msg-param-cumulative-months=1 :tmi.twitch.tv USERNOTICE #dallas";
+
+    let mut tags = Tags::default();
+    tags.insert("msg-id", TagValue::String("sub".into()));
+    tags.insert("msg-param-cumulative-months", TagValue::Number(1));
+
+    assert_eq!(
+        Message::parse(note)?,
+        Message::Usernotice {
+            tags: Some(tags),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "dallas",
-            msg: "Great stream -- keep it up!",
+            msg: None,
         }
     );
 
+    assert_eq!(Message::parse(note)?.unparse()?, note);
+
     Ok(())
 }
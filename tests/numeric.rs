@@ -0,0 +1,75 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn parse_welcome() -> Result<()> {
+    let burst = [
+        (":tmi.twitch.tv 001 ronni :Welcome, GLHF!", RPL_WELCOME),
+        (
+            ":tmi.twitch.tv 002 ronni :Your host is tmi.twitch.tv",
+            RPL_YOURHOST,
+        ),
+        (
+            ":tmi.twitch.tv 003 ronni :This server is rather new",
+            RPL_CREATED,
+        ),
+        (":tmi.twitch.tv 004 ronni :-", RPL_MYINFO),
+        (":tmi.twitch.tv 375 ronni :-", RPL_MOTDSTART),
+        (
+            ":tmi.twitch.tv 372 ronni :You are in a maze of twisty passages, all alike.",
+            RPL_MOTD,
+        ),
+        (":tmi.twitch.tv 376 ronni :>\r\n", RPL_ENDOFMOTD),
+        (
+            ":tmi.twitch.tv 421 ronni WHO :Unknown command",
+            ERR_UNKNOWNCOMMAND,
+        ),
+    ];
+
+    for (line, code) in burst.iter() {
+        let msg = Message::parse(line)?;
+
+        assert!(matches!(msg, Message::Numeric { code: c, .. } if c == *code));
+        assert_eq!(msg.params().and_then(|mut p| p.next()), Some("ronni"));
+        assert_eq!(msg.unparse()?, line.trim());
+    }
+
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv 001 ronni :Welcome, GLHF!")?,
+        Message::Numeric {
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
+            code: RPL_WELCOME,
+            params: "ronni :Welcome, GLHF!",
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_names() -> Result<()> {
+    let names1 = ":ronni.tmi.twitch.tv 353 ronni = #dallas :ronni fred wilma";
+    let names2 = ":ronni.tmi.twitch.tv 366 ronni #dallas :End of /NAMES list";
+
+    let msg1 = Message::parse(names1)?;
+
+    assert_eq!(
+        msg1.names().map(|n| n.collect::<Vec<_>>()),
+        Some(vec!["ronni", "fred", "wilma"])
+    );
+
+    assert_eq!(
+        msg1.params().map(|p| p.collect::<Vec<_>>()),
+        Some(vec!["ronni", "=", "#dallas", "ronni fred wilma"])
+    );
+
+    let msg2 = Message::parse(names2)?;
+
+    assert!(msg2.names().is_none());
+    assert_eq!(
+        msg2.params().map(|p| p.collect::<Vec<_>>()),
+        Some(vec!["ronni", "#dallas", "End of /NAMES list"])
+    );
+
+    Ok(())
+}
@@ -6,9 +6,39 @@ fn parse_part() -> Result<()> {
     let part1 = "PART #<channel>";
     let part2 = ":ronni!ronni@ronni.tmi.twitch.tv PART #dallas";
 
-    assert_eq!(Message::parse(part1)?, Message::Part { chan: "<channel>" });
+    assert_eq!(
+        Message::parse(part1)?,
+        Message::Part {
+            prefix: None,
+            chan: "<channel>"
+        }
+    );
 
-    assert_eq!(Message::parse(part2)?, Message::Part { chan: "dallas" });
+    assert_eq!(
+        Message::parse(part2)?,
+        Message::Part {
+            prefix: Some(Prefix::User {
+                nick: "ronni",
+                user: Some("ronni"),
+                host: Some("ronni.tmi.twitch.tv"),
+            }),
+            chan: "dallas"
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn part_membership() -> Result<()> {
+    let part1 = ":ronni!ronni@ronni.tmi.twitch.tv PART #dallas";
+    let part2 = "PART #dallas";
+
+    assert_eq!(Message::parse(part1)?.login(), Some("ronni"));
+    assert_eq!(Message::parse(part2)?.login(), None);
+
+    assert_eq!(Message::parse(part1)?.unparse()?, part1);
+    assert_eq!(Message::parse(part2)?.unparse()?, part2);
 
     Ok(())
 }
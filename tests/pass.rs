@@ -9,6 +9,7 @@ fn parse_pass() -> Result<()> {
     assert_eq!(
         Message::parse(pass1)?,
         Message::Pass {
+            prefix: None,
             pass: "oauth:mypassworddhasdsa"
         }
     );
@@ -16,6 +17,7 @@ fn parse_pass() -> Result<()> {
     assert_eq!(
         Message::parse(pass2)?,
         Message::Pass {
+            prefix: None,
             pass: "oauth:hello"
         }
     );
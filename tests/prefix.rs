@@ -0,0 +1,50 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn parse_prefix() -> Result<()> {
+    assert_eq!(
+        Prefix::parse("tmi.twitch.tv"),
+        Some(Prefix::Server("tmi.twitch.tv"))
+    );
+
+    assert_eq!(
+        Prefix::parse("ronni!ronni@ronni.tmi.twitch.tv"),
+        Some(Prefix::User {
+            nick: "ronni",
+            user: Some("ronni"),
+            host: Some("ronni.tmi.twitch.tv"),
+        })
+    );
+
+    assert_eq!(
+        Prefix::parse("ronni"),
+        Some(Prefix::User {
+            nick: "ronni",
+            user: None,
+            host: None,
+        })
+    );
+
+    assert_eq!(Prefix::parse(""), None);
+    assert_eq!(Prefix::parse("!ronni@ronni.tmi.twitch.tv"), None);
+    assert_eq!(Prefix::parse("ronni!@ronni.tmi.twitch.tv"), None);
+
+    Ok(())
+}
+
+#[test]
+fn unparse_prefix() -> Result<()> {
+    let msg1 = ":ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :hi";
+    let msg2 = "PRIVMSG #dallas :hi";
+
+    assert_eq!(Message::parse(msg1)?.unparse()?, msg1);
+    assert_eq!(Message::parse(msg2)?.unparse()?, msg2);
+
+    assert_eq!(
+        Message::parse(msg1)?.prefix().and_then(Prefix::nick),
+        Some("ronni")
+    );
+
+    Ok(())
+}
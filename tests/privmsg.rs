@@ -14,6 +14,7 @@ fn parse_privmsg() -> Result<()> {
         Message::parse(msg1)?,
         Message::Privmsg {
             tags: None,
+            prefix: None,
             chan: "<channel>",
             msg: "This is a sample message",
         }
@@ -23,6 +24,11 @@ fn parse_privmsg() -> Result<()> {
         Message::parse(msg2)?,
         Message::Privmsg {
             tags: None,
+            prefix: Some(Prefix::User {
+                nick: "<user>",
+                user: Some("<user>"),
+                host: Some("<user>.tmi.twitch.tv"),
+            }),
             chan: "<channel>",
             msg: "This is a sample message",
         }
@@ -30,29 +36,31 @@ fn parse_privmsg() -> Result<()> {
 
     let mut tags = Tags::default();
     tags.insert("badge-info", TagValue::None);
-    tags.insert("badges", TagValue::String("global_mod/1,turbo/1"));
-    tags.insert(
-        "color",
-        TagValue::Color(u32::from_str_radix("0D4200", 16).unwrap()),
-    );
-    tags.insert("display-name", TagValue::String("ronni"));
-    tags.insert("emotes", TagValue::String("25:0-4,12-16/1902:6-10"));
+    tags.insert("badges", TagValue::String("global_mod/1,turbo/1".into()));
+    tags.insert("color", TagValue::Color(Rgb::from_hex("#0D4200").unwrap()));
+    tags.insert("display-name", TagValue::String("ronni".into()));
+    tags.insert("emotes", TagValue::String("25:0-4,12-16/1902:6-10".into()));
     tags.insert(
         "id",
-        TagValue::String("b34ccfc7-4977-403a-8a94-33c6bac34fb8"),
+        TagValue::String("b34ccfc7-4977-403a-8a94-33c6bac34fb8".into()),
     );
     tags.insert("mod", TagValue::Boolean(false));
-    tags.insert("room-id", TagValue::Number(1337u32));
+    tags.insert("room-id", TagValue::String("1337".into()));
     tags.insert("subscriber", TagValue::Boolean(false));
     tags.insert("tmi-sent-ts", TagValue::Timestamp(1507246572675u64));
     tags.insert("turbo", TagValue::Boolean(true));
-    tags.insert("user-id", TagValue::Number(1337u32));
-    tags.insert("user-type", TagValue::String("global_mod"));
+    tags.insert("user-id", TagValue::String("1337".into()));
+    tags.insert("user-type", TagValue::String("global_mod".into()));
 
     assert_eq!(
         Message::parse(msg3)?,
         Message::Privmsg {
             tags: Some(tags),
+            prefix: Some(Prefix::User {
+                nick: "ronni",
+                user: Some("ronni"),
+                host: Some("ronni.tmi.twitch.tv"),
+            }),
             chan: "ronni",
             msg: "Kappa Keepo Kappa",
         }
@@ -60,3 +68,28 @@ fn parse_privmsg() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn parse_privmsg_without_prefix() -> Result<()> {
+    let msg = "@reply-parent-msg-id=b34ccfc7-4977-403a-8a94-33c6bac34fb8 PRIVMSG #dallas :hi";
+
+    let mut tags = Tags::default();
+    tags.insert(
+        "reply-parent-msg-id",
+        TagValue::String("b34ccfc7-4977-403a-8a94-33c6bac34fb8".into()),
+    );
+
+    assert_eq!(
+        Message::parse(msg)?,
+        Message::Privmsg {
+            tags: Some(tags),
+            prefix: None,
+            chan: "dallas",
+            msg: "hi",
+        }
+    );
+
+    assert_eq!(Message::parse(msg)?.unparse()?, msg);
+
+    Ok(())
+}
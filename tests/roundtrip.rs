@@ -0,0 +1,109 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn roundtrip() -> Result<()> {
+    let lines = [
+        "PING :tmi.twitch.tv",
+        "PONG",
+        "CAP REQ :twitch.tv/tags twitch.tv/commands",
+        ":tmi.twitch.tv CAP * ACK :twitch.tv/tags\r\n",
+        ":tmi.twitch.tv CAP * LS :twitch.tv/tags twitch.tv/commands twitch.tv/membership",
+        "CAP END",
+        "PASS oauth:abcdef",
+        "NICK ronni",
+        ":ronni!ronni@ronni.tmi.twitch.tv JOIN #dallas",
+        "PART #dallas",
+        "@badge-info=subscriber/16;badges=subscriber/12,bits/1000;color=#0D4200;display-name=ronni;\
+         emotes=25:0-4;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;room-id=1337;subscriber=1;\
+         tmi-sent-ts=1507246572675;turbo=0;user-id=1338;user-type= \
+         :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :Kappa hi",
+        "@reply-parent-msg-id=b34ccfc7-4977-403a-8a94-33c6bac34fb8 PRIVMSG #dallas :hi",
+        "@badges=staff/1;color=;display-name=ronni;emotes=;message-id=306;thread-id=1337_1338;\
+         turbo=0;user-id=1337;user-type=staff :ronni!ronni@ronni.tmi.twitch.tv WHISPER dallas :hello",
+        "@ban-duration=350;room-id=1337;target-user-id=1338;tmi-sent-ts=1642719320727 \
+         :tmi.twitch.tv CLEARCHAT #dallas :ronni",
+        "@login=ronni;target-msg-id=abc :tmi.twitch.tv CLEARMSG #dallas :HeyGuys",
+        ":tmi.twitch.tv HOSTTARGET #hosting_channel :- 123456",
+        "@msg-id=slow_off :tmi.twitch.tv NOTICE #dallas :This room is no longer in slow mode.",
+        ":tmi.twitch.tv NOTICE * :Login authentication failed",
+        ":tmi.twitch.tv RECONNECT",
+        "@emote-only=0;followers-only=-1;r9k=0;room-id=1337;slow=0;subs-only=0 :tmi.twitch.tv ROOMSTATE #dallas",
+        "@msg-id=resub;msg-param-cumulative-months=6;system-msg=ronni\\shas\\ssubscribed\\sfor\\s6\\smonths! \
+         :tmi.twitch.tv USERNOTICE #dallas :Great stream -- keep it up!",
+        "@msg-id=raid;msg-param-viewerCount=15 :tmi.twitch.tv USERNOTICE #dallas",
+        "@badge-info=;badges=;color=;display-name=ronni;emote-sets=0,33,50;mod=1;subscriber=0;turbo=0;user-type=mod \
+         :tmi.twitch.tv USERSTATE #dallas",
+        "@badge-info=;badges=;color=#0D4200;display-name=ronni;emote-sets=0;user-id=1337;user-type= \
+         :tmi.twitch.tv GLOBALUSERSTATE",
+        ":ronni.tmi.twitch.tv 353 ronni = #dallas :ronni fred wilma",
+        "\t:tmi.twitch.tv 376 ronni :>\r\n",
+    ];
+
+    for line in lines.iter() {
+        assert_eq!(Message::parse(line)?.unparse()?, line.trim());
+    }
+
+    assert_eq!(Message::parse("NICK")?.unparse()?, "NICK");
+    assert_eq!(Message::parse("PASS")?.unparse()?, "PASS");
+
+    // Tokens and viewers are normalized.
+    assert_eq!(
+        Message::parse("PING tmi.twitch.tv")?.unparse()?,
+        "PING :tmi.twitch.tv"
+    );
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv HOSTTARGET #a :b 01")?.unparse()?,
+        ":tmi.twitch.tv HOSTTARGET #a :b 1"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn roundtrip_tags() -> Result<()> {
+    // Order, valueless tags, lowercase colors and unusual escapes are kept as is.
+    let msg =
+        "@turbo;color=#0d4200;mod=1;display-name=ron\\ni;bits=0100;flags= PRIVMSG #dallas :hi";
+    let parsed = Message::parse(msg)?;
+
+    assert_eq!(parsed.unparse()?, msg);
+
+    let tags = parsed.tags().unwrap();
+
+    assert_eq!(
+        tags.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+        vec!["turbo", "color", "mod", "display-name", "bits", "flags"]
+    );
+    assert_eq!(
+        tags.get("color"),
+        Some(&TagValue::Color(Rgb::new(0x0D, 0x42, 0x00)))
+    );
+    assert_eq!(tags.raw("color"), Some("color=#0d4200"));
+    assert_eq!(tags.raw("turbo"), Some("turbo"));
+
+    Ok(())
+}
+
+#[test]
+fn roundtrip_edited_tags() -> Result<()> {
+    let mut tags = Tags::default();
+    tags.insert("display-name", TagValue::String("ronni".into()));
+    tags.insert("system-msg", TagValue::String("hi there; bye".into()));
+    tags.insert("bits", TagValue::Number(100));
+    tags.insert("display-name", TagValue::String("Ronni".into()));
+
+    let msg = Message::Privmsg {
+        tags: Some(tags),
+        prefix: None,
+        chan: "dallas",
+        msg: "cheer100",
+    };
+
+    assert_eq!(
+        msg.unparse()?,
+        "@display-name=Ronni;system-msg=hi\\sthere\\:\\sbye;bits=100 PRIVMSG #dallas :cheer100"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::io::Result;
+use tmi_parser::*;
+
+fn split(msg: &str) -> Vec<&str> {
+    Message::Privmsg {
+        tags: None,
+        prefix: None,
+        chan: "dallas",
+        msg,
+    }
+    .split_long()
+    .into_iter()
+    .map(|part| match part {
+        Message::Privmsg { msg, .. } => msg,
+        _ => unreachable!(),
+    })
+    .collect()
+}
+
+#[test]
+fn split_short() -> Result<()> {
+    assert_eq!(split("hi there "), vec!["hi there "]);
+    assert_eq!(split(""), vec![""]);
+
+    let join = Message::Join {
+        prefix: None,
+        chan: "dallas",
+    };
+    assert_eq!(join.split_long().len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn split_whitespace() -> Result<()> {
+    let text = format!("{} {}", "a".repeat(300), "b".repeat(300));
+
+    assert_eq!(split(&text), vec!["a".repeat(300), "b".repeat(300)]);
+
+    let text = "Kappa ".repeat(200);
+    let parts = split(&text);
+
+    assert_eq!(parts.len(), 3);
+    assert!(parts.iter().all(|part| part.starts_with("Kappa")));
+    assert!(parts.iter().all(|part| part.ends_with("Kappa")));
+    assert_eq!(parts.join(" "), text.trim());
+
+    Ok(())
+}
+
+#[test]
+fn split_graphemes() -> Result<()> {
+    // Each family emoji is 7 chars and 25 bytes joined by zero width joiners.
+    let family = "👨\u{200D}👩\u{200D}👧\u{200D}👦";
+    let text = family.repeat(40);
+    let parts = split(&text);
+
+    assert!(parts.len() > 1);
+    assert_eq!(parts.concat(), text);
+    assert!(parts
+        .iter()
+        .all(|part| part.len() % family.len() == 0 && part.starts_with(family)));
+
+    // Accented letters made of a combining mark, and flags made of two regional indicators.
+    for unit in ["e\u{301}", "🇮🇹", "👍🏽"].iter() {
+        let text = unit.repeat(400);
+        let parts = split(&text);
+
+        assert!(parts.len() > 1);
+        assert_eq!(parts.concat(), text);
+        assert!(parts.iter().all(|part| part.len() % unit.len() == 0));
+    }
+
+    // Marks of other scripts and Hangul jamo, shifted by padding so that cuts fall inside clusters.
+    for unit in ["कि", "ש\u{5C1}", "ا\u{64E}", "\u{1100}\u{1161}\u{11A8}"].iter() {
+        for pad in ["a", "aa"].iter() {
+            let text = format!("{}{}", pad, unit.repeat(300));
+            let parts = split(&text);
+
+            assert!(parts.len() > 1);
+            assert_eq!(parts.concat(), text);
+            assert_eq!((parts[0].len() - pad.len()) % unit.len(), 0);
+            assert!(parts[1..].iter().all(|part| part.len() % unit.len() == 0));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn split_encodable() -> Result<()> {
+    let text = "hé 😀 ".repeat(300);
+    let msg = Message::Privmsg {
+        tags: Some(Tags::default()),
+        prefix: Some(Prefix::User {
+            nick: "ronni",
+            user: Some("ronni"),
+            host: Some("ronni.tmi.twitch.tv"),
+        }),
+        chan: "dallas",
+        msg: &text,
+    };
+
+    let parts = msg.split_long();
+
+    assert!(parts.len() > 1);
+    assert!(parts[0].tags().is_some());
+    assert!(parts[1..].iter().all(|part| part.tags().is_none()));
+
+    for part in parts.iter() {
+        assert!(part.encode(Role::Client).is_ok());
+        assert!(part.encode(Role::Server).is_ok());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn split_large() -> Result<()> {
+    // Long texts are split in linear time.
+    let text = "Kappa hé 😀 ".repeat(150_000);
+    let parts = split(&text);
+
+    assert!(parts.len() > 4000);
+    assert_eq!(parts.join(" "), text.trim());
+    assert!(parts
+        .iter()
+        .all(|part| part.chars().count() <= MAX_MESSAGE_CHARS));
+
+    Ok(())
+}
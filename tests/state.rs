@@ -10,31 +10,36 @@ fn parse_roomstate() -> Result<()> {
     let room3 = ":tmi.twitch.tv ROOMSTATE #<channel>";
 
     let mut tags1 = Tags::default();
-    tags1.insert("emote-only", TagValue::String("<emote-only>"));
-    tags1.insert("followers-only", TagValue::String("<followers-only>"));
-    tags1.insert("r9k", TagValue::String("<r9k>"));
-    tags1.insert("slow", TagValue::String("<slow>"));
-    tags1.insert("subs-only", TagValue::String("<subs-only>"));
+    tags1.insert("emote-only", TagValue::String("<emote-only>".into()));
+    tags1.insert(
+        "followers-only",
+        TagValue::String("<followers-only>".into()),
+    );
+    tags1.insert("r9k", TagValue::String("<r9k>".into()));
+    tags1.insert("slow", TagValue::String("<slow>".into()));
+    tags1.insert("subs-only", TagValue::String("<subs-only>".into()));
 
     assert_eq!(
         Message::parse(room1)?,
         Message::Roomstate {
             tags: Some(tags1),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "<channel>",
         }
     );
 
     let mut tags2 = Tags::default();
     tags2.insert("emote-only", TagValue::Boolean(false));
-    tags2.insert("followers-only", TagValue::Boolean(false));
+    tags2.insert("followers-only", TagValue::Integer(0));
     tags2.insert("r9k", TagValue::Boolean(false));
-    tags2.insert("slow", TagValue::Boolean(false));
+    tags2.insert("slow", TagValue::Number(0));
     tags2.insert("subs-only", TagValue::Boolean(false));
 
     assert_eq!(
         Message::parse(room2)?,
         Message::Roomstate {
             tags: Some(tags2),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "dallas",
         }
     );
@@ -43,6 +48,7 @@ fn parse_roomstate() -> Result<()> {
         Message::parse(room3)?,
         Message::Roomstate {
             tags: None,
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "<channel>",
         }
     );
@@ -50,6 +56,47 @@ fn parse_roomstate() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn room_settings() -> Result<()> {
+    let join = Message::parse(
+        "@emote-only=0;followers-only=-1;r9k=0;room-id=1337;slow=0;subs-only=0 :tmi.twitch.tv ROOMSTATE #dallas",
+    )?;
+    let mut settings = join.room_settings().unwrap();
+
+    assert_eq!(
+        settings,
+        RoomSettings {
+            emote_only: Some(false),
+            followers_only: Some(-1),
+            r9k: Some(false),
+            slow: Some(0),
+            subs_only: Some(false),
+            room_id: Some("1337".into()),
+        }
+    );
+
+    let update =
+        Message::parse("@followers-only=10;room-id=1337 :tmi.twitch.tv ROOMSTATE #dallas")?;
+    let update = update.room_settings().unwrap();
+
+    assert_eq!(update.followers_only, Some(10));
+    assert_eq!(update.slow, None);
+
+    settings.merge(&update);
+
+    assert_eq!(settings.followers_only, Some(10));
+    assert_eq!(settings.slow, Some(0));
+    assert_eq!(settings.room_id.as_deref(), Some("1337"));
+
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv ROOMSTATE #dallas")?.room_settings(),
+        Some(RoomSettings::default())
+    );
+    assert_eq!(Message::parse("JOIN #dallas")?.room_settings(), None);
+
+    Ok(())
+}
+
 #[test]
 fn parse_userstate() -> Result<()> {
     let user1 = "@badge-info=<badge-info>;badges=<badges>;color=<color>;display-name=<display-name>;\
@@ -60,45 +107,44 @@ fn parse_userstate() -> Result<()> {
     let user3 = ":tmi.twitch.tv USERSTATE #<channel>";
 
     let mut tags1 = Tags::default();
-    tags1.insert("badge-info", TagValue::String("<badge-info>"));
-    tags1.insert("badges", TagValue::String("<badges>"));
-    tags1.insert("color", TagValue::String("<color>"));
-    tags1.insert("display-name", TagValue::String("<display-name>"));
-    tags1.insert("emote-sets", TagValue::String("<emote-sets>"));
-    tags1.insert("mod", TagValue::String("<mod>"));
-    tags1.insert("subscriber", TagValue::String("<subscriber>"));
-    tags1.insert("turbo", TagValue::String("<turbo>"));
-    tags1.insert("user-type", TagValue::String("<user-type>"));
+    tags1.insert("badge-info", TagValue::String("<badge-info>".into()));
+    tags1.insert("badges", TagValue::String("<badges>".into()));
+    tags1.insert("color", TagValue::String("<color>".into()));
+    tags1.insert("display-name", TagValue::String("<display-name>".into()));
+    tags1.insert("emote-sets", TagValue::String("<emote-sets>".into()));
+    tags1.insert("mod", TagValue::String("<mod>".into()));
+    tags1.insert("subscriber", TagValue::String("<subscriber>".into()));
+    tags1.insert("turbo", TagValue::String("<turbo>".into()));
+    tags1.insert("user-type", TagValue::String("<user-type>".into()));
 
     assert_eq!(
         Message::parse(user1)?,
         Message::Userstate {
             tags: Some(tags1),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "<channel>",
         }
     );
 
     let mut tags2 = Tags::default();
     tags2.insert("badge-info", TagValue::None);
-    tags2.insert("badges", TagValue::String("staff/1"));
-    tags2.insert(
-        "color",
-        TagValue::Color(u32::from_str_radix("0D4200", 16).unwrap()),
-    );
-    tags2.insert("display-name", TagValue::String("ronni"));
+    tags2.insert("badges", TagValue::String("staff/1".into()));
+    tags2.insert("color", TagValue::Color(Rgb::from_hex("#0D4200").unwrap()));
+    tags2.insert("display-name", TagValue::String("ronni".into()));
     tags2.insert(
         "emote-sets",
-        TagValue::String("0,33,50,237,793,2126,3517,4578,5569,9400,10337,12239"),
+        TagValue::String("0,33,50,237,793,2126,3517,4578,5569,9400,10337,12239".into()),
     );
     tags2.insert("mod", TagValue::Boolean(true));
     tags2.insert("subscriber", TagValue::Boolean(true));
     tags2.insert("turbo", TagValue::Boolean(true));
-    tags2.insert("user-type", TagValue::String("staff"));
+    tags2.insert("user-type", TagValue::String("staff".into()));
 
     assert_eq!(
         Message::parse(user2)?,
         Message::Userstate {
             tags: Some(tags2),
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "dallas",
         }
     );
@@ -107,6 +153,7 @@ fn parse_userstate() -> Result<()> {
         Message::parse(user3)?,
         Message::Userstate {
             tags: None,
+            prefix: Some(Prefix::Server("tmi.twitch.tv")),
             chan: "<channel>",
         }
     );
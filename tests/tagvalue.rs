@@ -19,3 +19,115 @@ fn tagvalue() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn tagvalue_unescape() -> Result<()> {
+    assert_eq!(unescape("ronni"), "ronni");
+    assert_eq!(unescape("a\\:b"), "a;b");
+    assert_eq!(unescape("a\\sb"), "a b");
+    assert_eq!(unescape("a\\\\b"), "a\\b");
+    assert_eq!(unescape("a\\rb"), "a\rb");
+    assert_eq!(unescape("a\\nb"), "a\nb");
+    assert_eq!(unescape("a\\bc"), "abc");
+    assert_eq!(unescape("abc\\"), "abc");
+    assert_eq!(unescape("abc\\\\\\"), "abc\\");
+    assert_eq!(unescape("\\"), "");
+
+    assert!(matches!(unescape("ronni"), std::borrow::Cow::Borrowed(_)));
+    assert!(matches!(unescape("a\\sb"), std::borrow::Cow::Owned(_)));
+
+    Ok(())
+}
+
+#[test]
+fn tagvalue_escape() -> Result<()> {
+    assert_eq!(escape("ronni"), "ronni");
+    assert_eq!(escape("a;b"), "a\\:b");
+    assert_eq!(escape("a b"), "a\\sb");
+    assert_eq!(escape("a\\b"), "a\\\\b");
+    assert_eq!(escape("a\rb"), "a\\rb");
+    assert_eq!(escape("a\nb"), "a\\nb");
+    assert_eq!(escape("abc\\"), "abc\\\\");
+
+    let val = "; \\\r\n";
+    assert_eq!(unescape(&escape(val)), val);
+
+    Ok(())
+}
+
+#[test]
+fn tagvalue_roundtrip() -> Result<()> {
+    let msg =
+        "@system-msg=ronni\\shas\\ssubscribed\\:\\sthanks! :tmi.twitch.tv USERNOTICE #dallas :hi";
+
+    let parsed = Message::parse(msg)?;
+
+    if let Message::Usernotice {
+        tags: Some(tags), ..
+    } = &parsed
+    {
+        assert_eq!(
+            tags.get("system-msg"),
+            Some(&TagValue::String("ronni has subscribed; thanks!".into()))
+        );
+    } else {
+        panic!("expected a Usernotice message");
+    }
+
+    assert_eq!(parsed.unparse()?, msg);
+
+    Ok(())
+}
+
+#[test]
+fn tagvalue_schema() -> Result<()> {
+    assert_eq!(TagValue::parse("mod", "1"), TagValue::Boolean(true));
+    assert_eq!(TagValue::parse("bits", "1"), TagValue::Number(1));
+    assert_eq!(TagValue::parse("bits", "100"), TagValue::Number(100));
+    assert_eq!(
+        TagValue::parse("followers-only", "-1"),
+        TagValue::Integer(-1)
+    );
+    assert_eq!(
+        TagValue::parse("followers-only", "10"),
+        TagValue::Integer(10)
+    );
+    assert_eq!(
+        TagValue::parse("tmi-sent-ts", "1507246572675"),
+        TagValue::Timestamp(1507246572675)
+    );
+    assert_eq!(
+        TagValue::parse("color", "#0D4200"),
+        TagValue::Color(Rgb::new(0x0D, 0x42, 0x00))
+    );
+
+    // Unknown and string tags are never sniffed.
+    assert_eq!(
+        TagValue::parse("display-name", "1337"),
+        TagValue::String("1337".into())
+    );
+    assert_eq!(
+        TagValue::parse("user-id", "1"),
+        TagValue::String("1".into())
+    );
+    assert_eq!(
+        TagValue::parse("custom-tag", "#0D4200"),
+        TagValue::String("#0D4200".into())
+    );
+
+    // Empty values are None whatever the type.
+    assert_eq!(TagValue::parse("color", ""), TagValue::None);
+    assert_eq!(TagValue::parse("display-name", ""), TagValue::None);
+
+    // Values not matching the declared type are kept as strings.
+    assert_eq!(TagValue::parse("mod", "2"), TagValue::String("2".into()));
+    assert_eq!(
+        TagValue::parse("color", "red"),
+        TagValue::String("red".into())
+    );
+
+    assert_eq!(TagKind::of("subscriber"), TagKind::Boolean);
+    assert_eq!(TagKind::of("room-id"), TagKind::String);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn privmsg_tags() -> Result<()> {
+    let msg = "@badge-info=subscriber/16;badges=subscriber/12,bits/1000;bits=100;color=#0D4200;\
+               display-name=ronni;emotes=;first-msg=1;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;\
+               reply-parent-msg-id=abc;reply-parent-user-login=dallas;reply-parent-msg-body=hi\\sthere;\
+               room-id=1337;subscriber=1;tmi-sent-ts=1507246572675;turbo=0;user-id=1338;user-type= \
+               :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :cheer100";
+    let msg = Message::parse(msg)?;
+    let tags = msg.privmsg_tags().unwrap();
+
+    assert_eq!(tags.user_id(), Some("1338"));
+    assert_eq!(tags.room_id(), Some("1337"));
+    assert_eq!(tags.display_name(), Some("ronni"));
+    assert_eq!(tags.color(), Some(Rgb::new(0x0D, 0x42, 0x00)));
+    assert_eq!(tags.bits(), Some(100));
+    assert_eq!(tags.tmi_sent_ts(), Some(1507246572675));
+    assert_eq!(tags.user_type(), None);
+    assert!(tags.first_msg());
+    assert!(tags.subscriber());
+    assert!(!tags.is_mod());
+    assert!(!tags.vip());
+    assert!(tags.emotes().unwrap().is_empty());
+    assert_eq!(tags.badge_info().unwrap().subscriber_months(), Some(16));
+
+    assert_eq!(
+        tags.reply_parent(),
+        Some(ReplyParent {
+            msg_id: "abc",
+            user_id: None,
+            user_login: Some("dallas"),
+            display_name: None,
+            msg_body: Some("hi there"),
+        })
+    );
+
+    let plain = Message::parse("@id=abc PRIVMSG #dallas :hi")?;
+
+    assert_eq!(plain.privmsg_tags().unwrap().reply_parent(), None);
+    assert_eq!(plain.privmsg_tags().unwrap().bits(), None);
+    assert!(Message::parse("PRIVMSG #dallas :hi")?
+        .privmsg_tags()
+        .is_none());
+    assert!(plain.roomstate_tags().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn state_tags() -> Result<()> {
+    let user = Message::parse(
+        "@badge-info=;badges=staff/1;color=#0D4200;display-name=ronni;emote-sets=0,33,50;\
+         mod=1;subscriber=1;turbo=1;user-type=staff :tmi.twitch.tv USERSTATE #dallas",
+    )?;
+    let tags = user.userstate_tags().unwrap();
+
+    assert!(tags.is_mod());
+    assert_eq!(tags.user_type(), Some("staff"));
+    assert_eq!(tags.emote_sets().collect::<Vec<_>>(), vec!["0", "33", "50"]);
+
+    let room = Message::parse(
+        "@emote-only=0;followers-only=-1;r9k=0;room-id=1337;slow=10;subs-only=1 :tmi.twitch.tv ROOMSTATE #dallas",
+    )?;
+    let tags = room.roomstate_tags().unwrap();
+
+    assert_eq!(tags.emote_only(), Some(false));
+    assert_eq!(tags.followers_only(), Some(-1));
+    assert_eq!(tags.slow(), Some(10));
+    assert_eq!(tags.subs_only(), Some(true));
+    assert_eq!(tags.room_id(), Some("1337"));
+
+    let partial = Message::parse("@room-id=1337;slow=0 :tmi.twitch.tv ROOMSTATE #dallas")?;
+
+    assert_eq!(partial.roomstate_tags().unwrap().r9k(), None);
+    assert_eq!(partial.roomstate_tags().unwrap().slow(), Some(0));
+
+    Ok(())
+}
+
+#[test]
+fn usernotice_tags() -> Result<()> {
+    let msg = Message::parse(
+        "@login=ronni;msg-id=resub;msg-param-cumulative-months=6;msg-param-sub-plan=Prime;\
+         system-msg=ronni\\shas\\ssubscribed! :tmi.twitch.tv USERNOTICE #dallas :Great stream!",
+    )?;
+    let tags = msg.usernotice_tags().unwrap();
+
+    assert_eq!(tags.login(), Some("ronni"));
+    assert_eq!(tags.msg_id(), Some("resub"));
+    assert_eq!(tags.system_msg(), Some("ronni has subscribed!"));
+    assert_eq!(tags.param("cumulative-months"), Some(&TagValue::Number(6)));
+    assert_eq!(
+        tags.param("sub-plan"),
+        Some(&TagValue::String("Prime".into()))
+    );
+    assert_eq!(tags.param("sub"), None);
+    assert_eq!(tags.param("login"), None);
+
+    Ok(())
+}
+
+#[test]
+fn clear_tags() -> Result<()> {
+    let chat = Message::parse(
+        "@ban-duration=600;room-id=1337;target-user-id=1338;tmi-sent-ts=1507246572675 \
+         :tmi.twitch.tv CLEARCHAT #dallas :ronni",
+    )?;
+    let tags = chat.clearchat_tags().unwrap();
+
+    assert_eq!(tags.ban_duration(), Some(600));
+    assert_eq!(tags.target_user_id(), Some("1338"));
+    assert_eq!(tags.tmi_sent_ts(), Some(1507246572675));
+
+    let msg = Message::parse(
+        "@login=ronni;room-id=;target-msg-id=abc-123-def :tmi.twitch.tv CLEARMSG #dallas :HeyGuys",
+    )?;
+    let tags = msg.clearmsg_tags().unwrap();
+
+    assert_eq!(tags.login(), Some("ronni"));
+    assert_eq!(tags.room_id(), None);
+    assert_eq!(tags.target_msg_id(), Some("abc-123-def"));
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn usernotice_subs() -> Result<()> {
+    let resub = "@badge-info=;badges=staff/1;login=ronni;msg-id=resub;msg-param-cumulative-months=6;\
+                 msg-param-streak-months=2;msg-param-should-share-streak=1;msg-param-sub-plan=Prime;\
+                 msg-param-sub-plan-name=Prime :tmi.twitch.tv USERNOTICE #dallas :Great stream -- keep it up!";
+    let subgift = "@login=tww2;msg-id=subgift;msg-param-months=1;msg-param-recipient-display-name=Mr_Woodchuck;\
+                   msg-param-recipient-id=89614178;msg-param-recipient-user-name=mr_woodchuck;msg-param-sub-plan=1000 \
+                   :tmi.twitch.tv USERNOTICE #forstycup";
+    let mystery = "@login=ronni;msg-id=anonsubmysterygift;msg-param-mass-gift-count=5;msg-param-sub-plan=3000 \
+                   :tmi.twitch.tv USERNOTICE #dallas";
+
+    assert_eq!(
+        Message::parse(resub)?.usernotice_event(),
+        Some(UsernoticeEvent::Resub {
+            months: Some(6),
+            streak: Some(2),
+            plan: Some(SubPlan::Prime),
+        })
+    );
+
+    assert_eq!(
+        Message::parse(subgift)?.usernotice_event(),
+        Some(UsernoticeEvent::Subgift {
+            anonymous: false,
+            months: Some(1),
+            gift_months: None,
+            plan: Some(SubPlan::Tier1),
+            recipient: Recipient {
+                id: Some("89614178"),
+                login: Some("mr_woodchuck"),
+                display_name: Some("Mr_Woodchuck"),
+            },
+        })
+    );
+
+    assert_eq!(
+        Message::parse(mystery)?.usernotice_event(),
+        Some(UsernoticeEvent::Submysterygift {
+            anonymous: true,
+            count: Some(5),
+            sender_count: None,
+            plan: Some(SubPlan::Tier3),
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn usernotice_others() -> Result<()> {
+    let raid =
+        "@msg-id=raid;msg-param-displayName=TWW2;msg-param-login=tww2;msg-param-viewerCount=15 \
+                :tmi.twitch.tv USERNOTICE #dallas";
+    let ritual = "@msg-id=ritual;msg-param-ritual-name=new_chatter :tmi.twitch.tv USERNOTICE #seventoes :HeyGuys";
+    let tier = "@msg-id=bitsbadgetier;msg-param-threshold=10000 :tmi.twitch.tv USERNOTICE #dallas";
+    let announce =
+        "@msg-id=announcement;msg-param-color=PURPLE :tmi.twitch.tv USERNOTICE #dallas :Hello!";
+    let unknown = "@msg-id=viewermilestone :tmi.twitch.tv USERNOTICE #dallas";
+
+    assert_eq!(
+        Message::parse(raid)?.usernotice_event(),
+        Some(UsernoticeEvent::Raid {
+            login: Some("tww2"),
+            display_name: Some("TWW2"),
+            viewers: Some(15),
+        })
+    );
+
+    assert_eq!(
+        Message::parse(ritual)?.usernotice_event(),
+        Some(UsernoticeEvent::Ritual {
+            name: Some("new_chatter")
+        })
+    );
+
+    assert_eq!(
+        Message::parse(tier)?.usernotice_event(),
+        Some(UsernoticeEvent::Bitsbadgetier {
+            threshold: Some(10000)
+        })
+    );
+
+    assert_eq!(
+        Message::parse(announce)?.usernotice_event(),
+        Some(UsernoticeEvent::Announcement {
+            color: Some(AnnouncementColor::Purple)
+        })
+    );
+
+    assert_eq!(
+        Message::parse(unknown)?.usernotice_event(),
+        Some(UsernoticeEvent::Unknown("viewermilestone"))
+    );
+
+    assert_eq!(
+        Message::parse(":tmi.twitch.tv USERNOTICE #dallas")?.usernotice_event(),
+        None
+    );
+    assert_eq!(
+        Message::parse("PRIVMSG #dallas :hi")?.usernotice_event(),
+        None
+    );
+
+    Ok(())
+}
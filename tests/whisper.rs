@@ -0,0 +1,67 @@
+use std::io::Result;
+use tmi_parser::*;
+
+#[test]
+fn parse_whisper() -> Result<()> {
+    let msg1 = ":<user>!<user>@<user>.tmi.twitch.tv WHISPER <to-user> :<message>";
+    let msg2 = "@badges=;color=#0D4200;display-name=ronni;emotes=;message-id=3;thread-id=1337_1338;\
+                turbo=0;user-id=1337;user-type= :ronni!ronni@ronni.tmi.twitch.tv WHISPER dallas :Hello there!\r\n";
+
+    assert_eq!(
+        Message::parse(msg1)?,
+        Message::Whisper {
+            tags: None,
+            from: Prefix::User {
+                nick: "<user>",
+                user: Some("<user>"),
+                host: Some("<user>.tmi.twitch.tv"),
+            },
+            to: "<to-user>",
+            msg: "<message>",
+        }
+    );
+
+    let mut tags = Tags::default();
+    tags.insert("badges", TagValue::None);
+    tags.insert("color", TagValue::Color(Rgb::from_hex("#0D4200").unwrap()));
+    tags.insert("display-name", TagValue::String("ronni".into()));
+    tags.insert("emotes", TagValue::None);
+    tags.insert("message-id", TagValue::String("3".into()));
+    tags.insert("thread-id", TagValue::String("1337_1338".into()));
+    tags.insert("turbo", TagValue::Boolean(false));
+    tags.insert("user-id", TagValue::String("1337".into()));
+    tags.insert("user-type", TagValue::None);
+
+    assert_eq!(
+        Message::parse(msg2)?,
+        Message::Whisper {
+            tags: Some(tags),
+            from: Prefix::User {
+                nick: "ronni",
+                user: Some("ronni"),
+                host: Some("ronni.tmi.twitch.tv"),
+            },
+            to: "dallas",
+            msg: "Hello there!",
+        }
+    );
+
+    assert_eq!(
+        Message::parse("WHISPER dallas :Hello there!"),
+        Err(ParseError::MissingPrefix {
+            offset: 0,
+            command: "WHISPER",
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn unparse_whisper() -> Result<()> {
+    let msg = ":ronni!ronni@ronni.tmi.twitch.tv WHISPER dallas :Hello there!";
+
+    assert_eq!(Message::parse(msg)?.unparse()?, msg);
+
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use std::io::Result;
+use tmi_parser::*;
+
+fn tags<'a>() -> Option<Tags<'a>> {
+    let mut tags = Tags::default();
+    tags.insert("display-name", TagValue::String("ronni".into()));
+    tags.insert("system-msg", TagValue::String("hi; there".into()));
+    tags.insert("color", TagValue::Color(Rgb::new(0x0D, 0x42, 0x00)));
+    tags.insert("mod", TagValue::Boolean(true));
+    tags.insert("tmi-sent-ts", TagValue::Timestamp(1507246572675));
+    Some(tags)
+}
+
+fn server<'a>() -> Option<Prefix<'a>> {
+    Some(Prefix::Server("tmi.twitch.tv"))
+}
+
+fn user<'a>() -> Prefix<'a> {
+    Prefix::User {
+        nick: "ronni",
+        user: Some("ronni"),
+        host: Some("ronni.tmi.twitch.tv"),
+    }
+}
+
+#[test]
+fn unparse_client() -> Result<()> {
+    let cases = [
+        (
+            Message::Ping {
+                prefix: None,
+                token: Some("tmi.twitch.tv"),
+            },
+            "PING :tmi.twitch.tv",
+        ),
+        (
+            Message::CapReq {
+                prefix: None,
+                req: "twitch.tv/tags twitch.tv/commands",
+            },
+            "CAP REQ :twitch.tv/tags twitch.tv/commands",
+        ),
+        (
+            Message::CapAck {
+                prefix: server(),
+                req: "twitch.tv/tags",
+            },
+            ":tmi.twitch.tv CAP * ACK :twitch.tv/tags",
+        ),
+        (
+            Message::Privmsg {
+                tags: None,
+                prefix: None,
+                chan: "dallas",
+                msg: "hi",
+            },
+            "PRIVMSG #dallas :hi",
+        ),
+    ];
+
+    for (msg, line) in cases.iter() {
+        assert_eq!(msg.unparse()?, *line);
+        assert_eq!(msg.unparse_line()?, format!("{}\r\n", line));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn unparse_every_variant() -> Result<()> {
+    let messages = vec![
+        Message::Ping {
+            prefix: None,
+            token: Some("tmi.twitch.tv"),
+        },
+        Message::Ping {
+            prefix: None,
+            token: None,
+        },
+        Message::Pong {
+            prefix: server(),
+            token: Some("tmi.twitch.tv"),
+        },
+        Message::CapReq {
+            prefix: None,
+            req: "twitch.tv/tags twitch.tv/commands",
+        },
+        Message::CapAck {
+            prefix: server(),
+            req: "twitch.tv/tags",
+        },
+        Message::CapNak {
+            prefix: server(),
+            req: "twitch.tv/foo",
+        },
+        Message::CapLs {
+            prefix: None,
+            version: None,
+            caps: None,
+            more: false,
+        },
+        Message::CapLs {
+            prefix: None,
+            version: Some("302"),
+            caps: None,
+            more: false,
+        },
+        Message::CapLs {
+            prefix: server(),
+            version: None,
+            caps: Some("twitch.tv/tags twitch.tv/commands"),
+            more: false,
+        },
+        Message::CapLs {
+            prefix: server(),
+            version: None,
+            caps: Some("twitch.tv/tags"),
+            more: true,
+        },
+        Message::CapList {
+            prefix: None,
+            caps: None,
+        },
+        Message::CapList {
+            prefix: server(),
+            caps: Some("twitch.tv/tags"),
+        },
+        Message::CapEnd { prefix: None },
+        Message::Pass {
+            prefix: None,
+            pass: "oauth:abcdef",
+        },
+        Message::Nick {
+            prefix: None,
+            nick: "ronni",
+        },
+        Message::Join {
+            prefix: Some(user()),
+            chan: "dallas",
+        },
+        Message::Part {
+            prefix: None,
+            chan: "dallas",
+        },
+        Message::Privmsg {
+            tags: tags(),
+            prefix: Some(user()),
+            chan: "dallas",
+            msg: "Kappa :) hi",
+        },
+        Message::Whisper {
+            tags: tags(),
+            from: user(),
+            to: "dallas",
+            msg: "hello there",
+        },
+        Message::Clearchat {
+            tags: tags(),
+            prefix: server(),
+            chan: "dallas",
+            usr: None,
+        },
+        Message::Clearchat {
+            tags: None,
+            prefix: server(),
+            chan: "dallas",
+            usr: Some("ronni"),
+        },
+        Message::Clearmsg {
+            tags: tags(),
+            prefix: server(),
+            chan: "dallas",
+            msg: "HeyGuys",
+        },
+        Message::HosttargetStart {
+            prefix: server(),
+            host: "dallas",
+            chan: "ronni",
+            view: Some(10),
+        },
+        Message::HosttargetStart {
+            prefix: server(),
+            host: "dallas",
+            chan: "ronni",
+            view: None,
+        },
+        Message::HosttargetEnd {
+            prefix: server(),
+            host: "dallas",
+            view: Some(0),
+        },
+        Message::HosttargetEnd {
+            prefix: server(),
+            host: "dallas",
+            view: None,
+        },
+        Message::Notice {
+            tags: tags(),
+            prefix: server(),
+            chan: Some("dallas"),
+            msg: "This room is no longer in slow mode.",
+        },
+        Message::Notice {
+            tags: None,
+            prefix: server(),
+            chan: None,
+            msg: "Login authentication failed",
+        },
+        Message::Reconnect { prefix: server() },
+        Message::Roomstate {
+            tags: tags(),
+            prefix: server(),
+            chan: "dallas",
+        },
+        Message::Usernotice {
+            tags: tags(),
+            prefix: server(),
+            chan: "dallas",
+            msg: Some("Great stream -- keep it up!"),
+        },
+        Message::Usernotice {
+            tags: tags(),
+            prefix: server(),
+            chan: "dallas",
+            msg: None,
+        },
+        Message::Userstate {
+            tags: tags(),
+            prefix: server(),
+            chan: "dallas",
+        },
+        Message::Numeric {
+            prefix: server(),
+            code: 1,
+            params: "ronni :Welcome, GLHF!",
+        },
+        Message::Numeric {
+            prefix: server(),
+            code: 376,
+            params: "",
+        },
+        Message::GlobalUserstate {
+            tags: tags(),
+            prefix: server(),
+        },
+    ];
+
+    for msg in messages.iter() {
+        let line = msg.unparse_line()?;
+
+        assert!(line.ends_with("\r\n"));
+        assert_eq!(Message::parse(&line)?, *msg, "{:?}", line);
+        assert_eq!(Message::parse(&line)?.unparse()?, line.trim_end());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use std::io::{self, Result};
+use tmi_parser::*;
+
+#[test]
+fn write_fmt() -> Result<()> {
+    let msg = "@badge-info=;color=#0D4200;display-name=ronni :ronni!ronni@ronni.tmi.twitch.tv PRIVMSG #dallas :hi";
+    let parsed = Message::parse(msg)?;
+
+    let mut buf = String::new();
+    parsed.write_to(&mut buf).unwrap();
+    assert_eq!(buf, msg);
+
+    // The buffer is reused as is.
+    buf.clear();
+    Message::parse("PING :tmi.twitch.tv")?
+        .reply()
+        .unwrap()
+        .write_to(&mut buf)
+        .unwrap();
+    assert_eq!(buf, "PONG :tmi.twitch.tv");
+
+    assert_eq!(parsed.to_string(), msg);
+    assert_eq!(format!("{}", parsed), parsed.unparse()?);
+
+    Ok(())
+}
+
+#[test]
+fn write_io() -> Result<()> {
+    let msg = ":tmi.twitch.tv CLEARCHAT #dallas :ronni";
+
+    let mut buf = Vec::new();
+    Message::parse(msg)?.write_io(&mut buf)?;
+    buf.extend_from_slice(b"\r\n");
+    Message::Join {
+        prefix: None,
+        chan: "dallas",
+    }
+    .write_io(&mut buf)?;
+
+    assert_eq!(
+        buf,
+        b":tmi.twitch.tv CLEARCHAT #dallas :ronni\r\nJOIN #dallas"
+    );
+
+    struct Full;
+
+    impl io::Write for Full {
+        fn write(&mut self, _: &[u8]) -> Result<usize> {
+            Err(io::Error::new(io::ErrorKind::WriteZero, "full"))
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    let err = Message::parse(msg)?.write_io(Full).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+
+    Ok(())
+}